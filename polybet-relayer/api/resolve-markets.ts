@@ -26,7 +26,7 @@ export default async function handler(req: any, res: any) {
 
     try {
         // 2. Fetch all unresolved markets
-        const openMarkets = await fetchOpenMarkets(program);
+        const openMarkets = await fetchOpenMarkets(program, oracleKeypair.publicKey);
         console.log(`🔎 Found ${openMarkets.length} open markets.`);
 
         const resolutionResults = [];
@@ -1,5 +1,5 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { Idl, Program } from "@coral-xyz/anchor";
 import { Connection, PublicKey, Keypair } from "@solana/web3.js";
 import idl from "../idl.json";
 
@@ -9,12 +9,20 @@ export function getProgram(connection: Connection, wallet: any) {
     const provider = new anchor.AnchorProvider(connection, wallet, {
         commitment: "confirmed",
     });
-    return new Program(idl as any, PROGRAM_ID, provider);
+    // The IDL is generated from the program (Anchor 0.30 format); pin it to the deployment this relayer serves
+    return new Program({ ...idl, address: PROGRAM_ID.toBase58() } as Idl, provider);
 }
 
-export async function fetchOpenMarkets(program: Program) {
-    const markets = await program.account.market.all();
-    return markets.filter((m: any) => !m.account.resolved && !m.account.paused);
+// Markets this relayer can still settle: open, and naming `oracle` as their resolver
+export async function fetchOpenMarkets(program: Program, oracle: PublicKey) {
+    const markets = await (program.account as any).market.all();
+    return markets.filter((m: any) =>
+        !m.account.resolved &&
+        !m.account.paused &&
+        !m.account.cancelled &&
+        !m.account.pendingResolution &&
+        m.account.oracle.equals(oracle)
+    );
 }
 
 export async function resolveMarketOnChain(
@@ -27,7 +35,7 @@ export async function resolveMarketOnChain(
         .resolveViaOracle(outcomeIndex)
         .accounts({
             market: marketPDA,
-            authority: authority.publicKey,
+            oracle: authority.publicKey,
         })
         .signers([authority])
         .rpc();
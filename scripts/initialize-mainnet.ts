@@ -8,7 +8,8 @@ import {
     SYSVAR_RENT_PUBKEY
 } from "@solana/web3.js";
 // import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token"; // Removed to avoid dependency issues
-const TOKEN_2022_PROGRAM_ID = new PublicKey("TokenzQdBnBLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+const TOKEN_2022_PROGRAM_ID = new PublicKey("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
 import * as fs from "fs";
 import * as path from "path";
 
@@ -17,6 +18,9 @@ const RPC_URL = "https://responsive-serene-mountain.solana-mainnet.quiknode.pro/
 const PROGRAM_ID = new PublicKey("8m7wUvDdNc7U8nyutZKPLM4zn5CXuJWXovpKE6PvuiEj");
 const MINT_ADDRESS = new PublicKey("4kTwv7sEEhdp9CZnw3B9h639HZwVygMmmxi6uuFLpump");
 const WALLET_PATH = path.join(process.env.HOME || "", ".config/solana/id.json");
+// Optimistic resolution: bond posted by proposers and disputers (6-decimal base units), and how long disputes stay open
+const RESOLUTION_BOND = new anchor.BN(1_000_000_000);
+const DISPUTE_WINDOW_SECONDS = new anchor.BN(86_400);
 
 // Load IDL (Use process.cwd since we are running from project root)
 const idlPath = path.join(process.cwd(), "src/idl/polybet.json");
@@ -48,8 +52,15 @@ async function main() {
         PROGRAM_ID
     );
 
+    // Protocol fees are paid to the authority's own token account for the mint (must already exist)
+    const [feeRecipient] = PublicKey.findProgramAddressSync(
+        [wallet.publicKey.toBuffer(), TOKEN_2022_PROGRAM_ID.toBuffer(), MINT_ADDRESS.toBuffer()],
+        ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
     console.log("Config PDA:", configPda.toBase58());
     console.log("Vault PDA:", vaultPda.toBase58());
+    console.log("Fee Recipient:", feeRecipient.toBase58());
 
     try {
         console.log("\n--- Checking Account Status ---");
@@ -63,11 +74,12 @@ async function main() {
         // Build Instruction
         // Using 'any' cast to bypass "Type instantiation is excessively deep" errors
         const tx = await (program.methods as any)
-            .initializeProtocol()
+            .initializeProtocol(RESOLUTION_BOND, DISPUTE_WINDOW_SECONDS)
             .accounts({
                 config: configPda,
                 treasuryVault: vaultPda,
                 mint: MINT_ADDRESS,
+                feeRecipient,
                 authority: wallet.publicKey,
                 systemProgram: SystemProgram.programId,
                 tokenProgram: TOKEN_2022_PROGRAM_ID,
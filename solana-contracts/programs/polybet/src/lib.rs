@@ -1,133 +1,796 @@
+// Anchor 0.30's `#[program]` expands cfgs (`target_os = "solana"`, `custom-heap`, ...) this crate doesn't declare
+#![allow(unexpected_cfgs)]
+
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, Transfer};
+use anchor_lang::solana_program::hash::hash;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
 use solana_program::pubkey;
 
+pub mod lmsr;
+pub mod pyth;
+pub mod validation;
+
 declare_id!("8m7wUvDdNc7U8nyutZKPLM4zn5CXuJWXovpKE6PvuiEj");
 
-const TOKEN_2022_ID: Pubkey = pubkey!("TokenzQdBnBLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+const TOKEN_2022_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Protocol (Buyback Fund) share of every bet unless changed through `update_fees`
+pub const DEFAULT_PROTOCOL_FEE_BPS: u16 = 1_000;
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 2_000;
+pub const MAX_CREATOR_FEE_BPS: u16 = 1_000;
+
+/// Fee kept by the vault when a position is sold back before the market ends (3%)
+pub const EXIT_FEE_BPS: u64 = 300;
 
 #[program]
 pub mod polybet {
     use super::*;
 
-    pub fn initialize_protocol(ctx: Context<InitializeProtocol>) -> Result<()> {
+    pub fn initialize_protocol(ctx: Context<InitializeProtocol>, resolution_bond: u64, dispute_window: i64) -> Result<()> {
         require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        require!(resolution_bond > 0, PolybetError::InvalidResolutionBond);
+        require!(dispute_window > 0, PolybetError::InvalidDisputeWindow);
         
         let config = &mut ctx.accounts.config;
         config.authority = ctx.accounts.authority.key();
+        config.pending_authority = None;
+        config.mint = ctx.accounts.mint.key();
+        config.resolution_bond = resolution_bond;
+        config.dispute_window = dispute_window;
+        config.protocol_fee_bps = DEFAULT_PROTOCOL_FEE_BPS;
+        config.creator_fee_bps = 0;
+        config.fee_recipient = ctx.accounts.fee_recipient.key();
+        config.bonds_outstanding = 0;
+        config.min_creator_balance = 0;
+        config.creation_bond = 0;
+        config.fee_vault_bump = 0;
+        config.total_burned = 0;
         config.vault_bump = ctx.bumps.treasury_vault;
+        config.paused = false;
         config.bump = ctx.bumps.config;
 
         Ok(())
     }
 
     /// 2. Initialize Market (No token movement)
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_market(
         ctx: Context<InitializeMarket>, 
         question: String, 
         end_timestamp: i64, 
         outcomes_count: u8,
         virtual_liquidity: u64,
-        weights: [u32; 8]
+        weights: [u32; 8],
+        oracle: Pubkey,
+        outcome_names: Vec<String>,
+        min_bet: u64,
+        max_bet: u64,
+        polymarket_id: String,
+        metadata_url: String,
+        kind: MarketKind,
+        pricing: PricingMode
     ) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        require!(ctx.accounts.creator_token.amount >= ctx.accounts.config.min_creator_balance, PolybetError::CreatorBalanceTooLow);
+        validation::check_market(&validation::MarketParams {
+            question: &question,
+            end_timestamp,
+            outcomes_count,
+            virtual_liquidity,
+            weights: &weights,
+            outcome_names: &outcome_names,
+            min_bet,
+            max_bet,
+            polymarket_id: &polymarket_id,
+            metadata_url: &metadata_url,
+            kind: &kind,
+            pricing: &pricing,
+        }, Clock::get()?.unix_timestamp)?;
+
         let market = &mut ctx.accounts.market;
         market.authority = ctx.accounts.authority.key();
+        market.pending_authority = None;
+        market.oracle = oracle;
         market.question = question;
         market.end_timestamp = end_timestamp;
         market.outcomes_count = outcomes_count;
+        market.outcome_names = outcome_names;
         market.total_pot = virtual_liquidity;
+        market.min_bet = min_bet;
+        market.max_bet = max_bet;
+        market.polymarket_id = polymarket_id;
+        market.metadata_url = metadata_url;
+        market.kind = kind;
+        market.pricing = pricing;
+        market.shares = [0; 8];
+        market.outcome_mints_created = 0;
+        market.outcome_mint_bumps = [0; 8];
+        market.complete_sets = 0;
+        market.creator_fees = 0;
+        market.creator_fees_claimed = false;
+        market.deposits = 0;
+        market.payouts_owed = [0; 8];
         
-        let mut total_weight = 0u32;
-        for i in 0..outcomes_count as usize {
-            total_weight += weights[i];
-        }
-        for i in 0..outcomes_count as usize {
+        // Checked above: at most `MAX_OUTCOMES` weights whose sum fits in u32
+        let weights = &weights[..outcomes_count as usize];
+        let total_weight: u32 = weights.iter().sum();
+        for (total, weight) in market.outcome_totals.iter_mut().zip(weights) {
             if total_weight > 0 {
-                market.outcome_totals[i] = (virtual_liquidity as u128)
-                    .checked_mul(weights[i] as u128).unwrap()
+                *total = (virtual_liquidity as u128)
+                    .checked_mul(*weight as u128).unwrap()
                     .checked_div(total_weight as u128).unwrap() as u64;
             }
         }
         market.resolved = false;
+        market.cancelled = false;
+        market.pending_resolution = false;
+        market.paused = false;
+        market.vault_bump = ctx.bumps.market_vault;
         market.bump = ctx.bumps.market;
+
+        // Creation bond waits in the market's own vault until the market resolves or is cancelled,
+        // next to the LMSR subsidy that backs the market maker's worst-case loss
+        market.creation_bond = ctx.accounts.config.creation_bond;
+        market.subsidy = match pricing {
+            PricingMode::Lmsr { b } => lmsr::max_loss(b, outcomes_count).ok_or(PolybetError::MathOverflow)?,
+            _ => 0,
+        };
+        market.protocol_fee_bps = ctx.accounts.config.protocol_fee_bps;
+        let escrow = market.creation_bond.checked_add(market.subsidy).ok_or(PolybetError::MathOverflow)?;
+        token_interface::transfer_checked(CpiContext::new(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.creator_token.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.market_vault.to_account_info(), authority: ctx.accounts.authority.to_account_info() }), 
+            escrow, ctx.accounts.mint.decimals)?;
+        Ok(())
+    }
+
+    /// Index Market (Lookup PDA from Polymarket ID to the on-chain market, curated by the protocol)
+    pub fn index_market(ctx: Context<IndexMarket>) -> Result<()> {
+        require!(!ctx.accounts.market.polymarket_id.is_empty(), PolybetError::MissingPolymarketId);
+        let index = &mut ctx.accounts.market_index;
+        index.market = ctx.accounts.market.key();
+        index.bump = ctx.bumps.market_index;
         Ok(())
     }
 
-    /// 3. Place Bet (90/10 Split Implemented)
-    pub fn place_vote(ctx: Context<PlaceVote>, outcome_index: u8, amount: u64) -> Result<()> {
+    /// Point an existing Polymarket ID entry at another market with the same ID (e.g. the real mirror)
+    pub fn reindex_market(ctx: Context<ReindexMarket>) -> Result<()> {
+        require!(!ctx.accounts.market.polymarket_id.is_empty(), PolybetError::MissingPolymarketId);
+        ctx.accounts.market_index.market = ctx.accounts.market.key();
+        Ok(())
+    }
+
+    /// 3. Place Bet (Winners/Protocol split from config). `nonce` lets one wallet hold several positions per market.
+    pub fn place_vote(ctx: Context<PlaceVote>, outcome_index: u8, amount: u64, nonce: u64) -> Result<()> {
         require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        require!(!ctx.accounts.config.paused, PolybetError::Paused);
         let market = &mut ctx.accounts.market;
+        require!(!market.paused, PolybetError::Paused);
         let clock = Clock::get()?;
+        require!(!market.cancelled, PolybetError::MarketCancelled);
+        require!(!market.resolved && !market.pending_resolution, PolybetError::AlreadyResolved);
         require!(clock.unix_timestamp < market.end_timestamp, PolybetError::MarketEnded);
+        require!(amount >= market.min_bet, PolybetError::BetTooSmall);
+        require!(amount <= market.max_bet, PolybetError::BetTooLarge);
+        validation::check_outcome(market.outcomes_count, outcome_index)?;
+        let protocol_fee_bps = ctx.accounts.config.protocol_fee_bps as u128;
+        // Creator's cut is set aside first, the rest is the stake that gets priced
+        let creator_fee = (amount as u128).checked_mul(ctx.accounts.config.creator_fee_bps as u128).unwrap().checked_div(10_000).unwrap() as u64;
+        let stake = amount - creator_fee;
         
-        // 90/10 Split: 90% to winners, 10% to Buyback Fund (Dev Vault)
-        let total_payout = if market.outcome_totals[outcome_index as usize] > 0 {
-            (amount as u128)
-                .checked_mul(90).unwrap() 
-                .checked_mul(market.total_pot as u128).unwrap()
-                .checked_div(100).unwrap()
-                .checked_div(market.outcome_totals[outcome_index as usize] as u128).unwrap() as u64
-        } else {
-            amount
+        let (total_payout, dev_fee) = match market.pricing {
+            // Winners/Protocol Split: protocol share goes to the Buyback Fund (fee vault, see `burn_protocol_fees`)
+            PricingMode::FixedOdds => {
+                let total_payout = if market.outcome_totals[outcome_index as usize] > 0 {
+                    (stake as u128)
+                        .checked_mul(10_000 - protocol_fee_bps).unwrap() 
+                        .checked_mul(market.total_pot as u128).unwrap()
+                        .checked_div(10_000).unwrap()
+                        .checked_div(market.outcome_totals[outcome_index as usize] as u128).unwrap() as u64
+                } else {
+                    stake
+                };
+                // Protocol Tax for Buyback
+                let dev_fee = (total_payout as u128).checked_mul(protocol_fee_bps).unwrap().checked_div(10_000).unwrap() as u64;
+                (total_payout, dev_fee)
+            }
+            // Stake net of the protocol fee buys shares on the LMSR curve, each winning share pays 1 token
+            PricingMode::Lmsr { b } => {
+                let net = (stake as u128).checked_mul(10_000 - protocol_fee_bps).unwrap().checked_div(10_000).unwrap() as u64;
+                let n = market.outcomes_count as usize;
+                let bought = lmsr::shares_for_cost(&market.shares[..n], b, outcome_index as usize, net).ok_or(PolybetError::MathOverflow)?;
+                market.shares[outcome_index as usize] = market.shares[outcome_index as usize].checked_add(bought).unwrap();
+                (bought, stake - net)
+            }
+            // Settled from final totals at claim time
+            PricingMode::Parimutuel => (0, 0),
         };
 
-        // Move tokens to Global Treasury
-        token_interface::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), 
-            token_interface::Transfer { from: ctx.accounts.user_token.to_account_info(), to: ctx.accounts.treasury_vault.to_account_info(), authority: ctx.accounts.user.to_account_info() }), 
-            amount)?;
+        // Move tokens to Market Vault
+        token_interface::transfer_checked(CpiContext::new(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.user_token.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.market_vault.to_account_info(), authority: ctx.accounts.user.to_account_info() }), 
+            amount, ctx.accounts.mint.decimals)?;
 
         let vote = &mut ctx.accounts.vote;
         vote.user = ctx.accounts.user.key();
         vote.market = market.key();
         vote.outcome_index = outcome_index;
+        vote.nonce = nonce;
         vote.amount = amount;
         vote.locked_payout = total_payout;
-        
-        // Solid 10% Protocol Tax for Buyback
-        vote.locked_dev_fee = (total_payout as u128).checked_mul(11).unwrap().checked_div(100).unwrap() as u64; // Approx 10% of losing pot equivalent
+        vote.locked_creator_fee = creator_fee;
+        vote.locked_dev_fee = dev_fee;
+        vote.bump = ctx.bumps.vote;
+
+        market.deposits = market.deposits.checked_add(amount).unwrap();
+        market.payouts_owed[outcome_index as usize] = market.payouts_owed[outcome_index as usize].checked_add(vote.owed(&market.pricing)).unwrap();
+        market.total_pot = market.total_pot.checked_add(stake).unwrap();
+        market.outcome_totals[outcome_index as usize] = market.outcome_totals[outcome_index as usize].checked_add(stake).unwrap();
+        market.creator_fees = market.creator_fees.checked_add(creator_fee).unwrap();
+        Ok(())
+    }
+
+    /// Cash Out (Sell a position back before `end_timestamp` at current implied odds, minus exit fee)
+    pub fn cash_out(ctx: Context<CashOut>) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        require!(!ctx.accounts.config.paused, PolybetError::Paused);
+        let market = &mut ctx.accounts.market;
+        let vote = &ctx.accounts.vote;
+        let i = vote.outcome_index as usize;
+        require!(!market.paused, PolybetError::Paused);
+        require!(!market.cancelled, PolybetError::MarketCancelled);
+        require!(!market.resolved && !market.pending_resolution, PolybetError::AlreadyResolved);
+        require!(!vote.claimed, PolybetError::AlreadyClaimed);
+        require!(Clock::get()?.unix_timestamp < market.end_timestamp, PolybetError::MarketEnded);
+
+        let value = market.exit_value(vote).ok_or(PolybetError::MathOverflow)?;
+        if let PricingMode::Lmsr { .. } = market.pricing {
+            market.shares[i] = market.shares[i].checked_sub(vote.locked_payout).unwrap();
+        }
+        let payout = (value as u128)
+            .checked_mul((10_000 - EXIT_FEE_BPS) as u128).unwrap()
+            .checked_div(10_000).unwrap() as u64;
 
-        market.total_pot = market.total_pot.checked_add(amount).unwrap();
-        market.outcome_totals[outcome_index as usize] = market.outcome_totals[outcome_index as usize].checked_add(amount).unwrap();
+        market.total_pot = market.total_pot.checked_sub(vote.stake()).unwrap();
+        market.outcome_totals[i] = market.outcome_totals[i].checked_sub(vote.stake()).unwrap();
+        market.deposits = market.deposits.checked_sub(vote.amount).unwrap();
+        market.payouts_owed[i] = market.payouts_owed[i].checked_sub(vote.owed(&market.pricing)).unwrap();
+
+        let market_key = ctx.accounts.market.key();
+        let seeds = &[b"market_vault".as_ref(), market_key.as_ref(), &[ctx.accounts.market.vault_bump]];
+        let signer = &[&seeds[..]];
+        token_interface::transfer_checked(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.market_vault.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.user_token.to_account_info(), authority: ctx.accounts.market_vault.to_account_info() }, 
+            signer), payout, ctx.accounts.mint.decimals)?;
         Ok(())
     }
 
-    /// 4. Claim Winnings (Paid from Global Vault)
+    /// 4. Claim Winnings (Paid from Market Vault)
     pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
         require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
         let (payout, d_fee) = {
             let market = &ctx.accounts.market;
             let vote = &ctx.accounts.vote;
+            require!(!ctx.accounts.config.paused && !market.paused, PolybetError::Paused);
             require!(market.resolved, PolybetError::MarketActive);
             require!(!vote.claimed, PolybetError::AlreadyClaimed);
             require!(vote.outcome_index == market.winner_index.unwrap(), PolybetError::InvalidOutcome);
-            (vote.locked_payout, vote.locked_dev_fee)
+            match market.pricing {
+                // Pro-rata share of the final pool net of the protocol fee, never more than the pool
+                PricingMode::Parimutuel => {
+                    let winning_total = market.outcome_totals[vote.outcome_index as usize] as u128;
+                    let protocol_fee_bps = market.protocol_fee_bps as u128;
+                    let pool_after_fees = (market.total_pot as u128).checked_mul(10_000 - protocol_fee_bps).unwrap().checked_div(10_000).unwrap();
+                    let fees = market.total_pot as u128 - pool_after_fees;
+                    let payout = (vote.stake() as u128).checked_mul(pool_after_fees).unwrap().checked_div(winning_total).unwrap() as u64;
+                    let d_fee = (vote.stake() as u128).checked_mul(fees).unwrap().checked_div(winning_total).unwrap() as u64;
+                    (payout, d_fee)
+                }
+                _ => (vote.locked_payout, vote.locked_dev_fee),
+            }
         };
 
-        let seeds = &[b"treasury".as_ref(), &[ctx.accounts.config.vault_bump]];
+        let market_key = ctx.accounts.market.key();
+        let seeds = &[b"market_vault".as_ref(), market_key.as_ref(), &[ctx.accounts.market.vault_bump]];
         let signer = &[&seeds[..]];
 
         let cpi_program = ctx.accounts.token_program.to_account_info();
-        token_interface::transfer(CpiContext::new_with_signer(cpi_program.clone(), token_interface::Transfer { from: ctx.accounts.treasury_vault.to_account_info(), to: ctx.accounts.user_token.to_account_info(), authority: ctx.accounts.treasury_vault.to_account_info() }, signer), payout)?;
-        token_interface::transfer(CpiContext::new_with_signer(cpi_program.clone(), token_interface::Transfer { from: ctx.accounts.treasury_vault.to_account_info(), to: ctx.accounts.dev_token.to_account_info(), authority: ctx.accounts.treasury_vault.to_account_info() }, signer), d_fee)?;
+        token_interface::transfer_checked(CpiContext::new_with_signer(cpi_program.clone(), token_interface::TransferChecked { from: ctx.accounts.market_vault.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.user_token.to_account_info(), authority: ctx.accounts.market_vault.to_account_info() }, signer), payout, ctx.accounts.mint.decimals)?;
+        token_interface::transfer_checked(CpiContext::new_with_signer(cpi_program.clone(), token_interface::TransferChecked { from: ctx.accounts.market_vault.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.dev_token.to_account_info(), authority: ctx.accounts.market_vault.to_account_info() }, signer), d_fee, ctx.accounts.mint.decimals)?;
 
+        let market = &mut ctx.accounts.market;
+        let i = ctx.accounts.vote.outcome_index as usize;
+        market.payouts_owed[i] = market.payouts_owed[i].checked_sub(ctx.accounts.vote.owed(&market.pricing)).unwrap();
         ctx.accounts.vote.claimed = true;
         Ok(())
     }
 
     pub fn resolve_market(ctx: Context<ResolveMarket>, winner_index: u8) -> Result<()> {
         let market = &mut ctx.accounts.market;
+        require!(!market.cancelled, PolybetError::MarketCancelled);
+        require!(!market.resolved, PolybetError::AlreadyResolved);
+        require!(!market.pending_resolution, PolybetError::ResolutionPending);
+        validation::check_winner(market.outcomes_count, winner_index)?;
         market.resolved = true;
         market.winner_index = Some(winner_index);
         Ok(())
     }
 
-    pub fn sweep_profit(ctx: Context<SweepProfit>, amount: u64) -> Result<()> {
+    /// 5. Resolve via Oracle (Automated resolver, no creator keys needed)
+    pub fn resolve_via_oracle(ctx: Context<ResolveViaOracle>, winner_index: u8) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(!market.cancelled, PolybetError::MarketCancelled);
+        require!(!market.resolved, PolybetError::AlreadyResolved);
+        require!(!market.pending_resolution, PolybetError::ResolutionPending);
+        validation::check_winner(market.outcomes_count, winner_index)?;
+        market.resolved = true;
+        market.winner_index = Some(winner_index);
+        Ok(())
+    }
+
+    /// Resolve with Price Feed (Permissionless, settles threshold markets from Pyth)
+    pub fn resolve_with_price_feed(ctx: Context<ResolveWithPriceFeed>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(!market.cancelled, PolybetError::MarketCancelled);
+        require!(!market.resolved, PolybetError::AlreadyResolved);
+        require!(!market.pending_resolution, PolybetError::ResolutionPending);
+        let MarketKind::PriceThreshold { feed_id, strike, expo, comparison, settlement_timestamp } = market.kind else {
+            return err!(PolybetError::InvalidPriceMarket);
+        };
+
+        let update = pyth::PriceUpdateV2::try_from_slice_checked(&ctx.accounts.price_account.try_borrow_data()?)?;
+        let winner_index = pyth::settle_threshold(&update.price_message, &feed_id, strike, expo, comparison, settlement_timestamp, Clock::get()?.unix_timestamp)?;
+
+        market.resolved = true;
+        market.winner_index = Some(winner_index);
+        Ok(())
+    }
+
+    /// Optimistic Resolution: propose with a bond, open the dispute window
+    pub fn propose_resolution(ctx: Context<ProposeResolution>, winner_index: u8) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+        require!(!market.cancelled, PolybetError::MarketCancelled);
+        require!(!market.resolved, PolybetError::AlreadyResolved);
+        require!(clock.unix_timestamp >= market.end_timestamp, PolybetError::MarketActive);
+        validation::check_winner(market.outcomes_count, winner_index)?;
+
+        let bond = ctx.accounts.config.resolution_bond;
+        token_interface::transfer_checked(CpiContext::new(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.proposer_token.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.treasury_vault.to_account_info(), authority: ctx.accounts.proposer.to_account_info() }), 
+            bond, ctx.accounts.mint.decimals)?;
+
+        let resolution = &mut ctx.accounts.resolution;
+        resolution.market = market.key();
+        resolution.proposer = ctx.accounts.proposer.key();
+        resolution.proposed_index = winner_index;
+        resolution.disputer = None;
+        resolution.bond = bond;
+        resolution.dispute_deadline = clock.unix_timestamp.checked_add(ctx.accounts.config.dispute_window).unwrap();
+        resolution.settled = false;
+        resolution.bump = ctx.bumps.resolution;
+
+        let config = &mut ctx.accounts.config;
+        config.bonds_outstanding = config.bonds_outstanding.checked_add(bond).unwrap();
+
+        market.pending_resolution = true;
+        Ok(())
+    }
+
+    /// Dispute with a matching counter-bond (within the window)
+    pub fn dispute_resolution(ctx: Context<DisputeResolution>) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        let resolution = &mut ctx.accounts.resolution;
+        require!(!resolution.settled, PolybetError::ResolutionSettled);
+        require!(resolution.disputer.is_none(), PolybetError::AlreadyDisputed);
+        require!(Clock::get()?.unix_timestamp < resolution.dispute_deadline, PolybetError::DisputeWindowClosed);
+
+        token_interface::transfer_checked(CpiContext::new(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.disputer_token.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.treasury_vault.to_account_info(), authority: ctx.accounts.disputer.to_account_info() }), 
+            resolution.bond, ctx.accounts.mint.decimals)?;
+
+        resolution.disputer = Some(ctx.accounts.disputer.key());
+        let config = &mut ctx.accounts.config;
+        config.bonds_outstanding = config.bonds_outstanding.checked_add(resolution.bond).unwrap();
+        Ok(())
+    }
+
+    /// Finalize an undisputed proposal once the window has passed (permissionless)
+    pub fn finalize_resolution(ctx: Context<FinalizeResolution>) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        let resolution = &mut ctx.accounts.resolution;
+        require!(!resolution.settled, PolybetError::ResolutionSettled);
+        require!(resolution.disputer.is_none(), PolybetError::AlreadyDisputed);
+        require!(Clock::get()?.unix_timestamp >= resolution.dispute_deadline, PolybetError::DisputeWindowOpen);
+
+        let seeds = &[b"vault".as_ref(), &[ctx.accounts.config.vault_bump]];
+        let signer = &[&seeds[..]];
+        token_interface::transfer_checked(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.treasury_vault.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.proposer_token.to_account_info(), authority: ctx.accounts.treasury_vault.to_account_info() }, 
+            signer), resolution.bond, ctx.accounts.mint.decimals)?;
+
+        resolution.settled = true;
+        let config = &mut ctx.accounts.config;
+        config.bonds_outstanding = config.bonds_outstanding.checked_sub(resolution.bond).unwrap();
+        let market = &mut ctx.accounts.market;
+        market.pending_resolution = false;
+        market.resolved = true;
+        market.winner_index = Some(resolution.proposed_index);
+        Ok(())
+    }
+
+    /// Arbiter settles a disputed proposal; the losing bond goes to the winning side
+    pub fn settle_dispute(ctx: Context<SettleDispute>, winner_index: u8) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        let resolution = &mut ctx.accounts.resolution;
+        require!(!resolution.settled, PolybetError::ResolutionSettled);
+        let disputer = resolution.disputer.ok_or(PolybetError::NotDisputed)?;
+        validation::check_winner(ctx.accounts.market.outcomes_count, winner_index)?;
+
+        let bond_winner = if winner_index == resolution.proposed_index { resolution.proposer } else { disputer };
+        require_keys_eq!(ctx.accounts.bond_winner_token.owner, bond_winner, PolybetError::Unauthorized);
+
+        let seeds = &[b"vault".as_ref(), &[ctx.accounts.config.vault_bump]];
+        let signer = &[&seeds[..]];
+        let bonds = resolution.bond.checked_mul(2).unwrap();
+        token_interface::transfer_checked(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.treasury_vault.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.bond_winner_token.to_account_info(), authority: ctx.accounts.treasury_vault.to_account_info() }, 
+            signer), bonds, ctx.accounts.mint.decimals)?;
+
+        resolution.settled = true;
+        let config = &mut ctx.accounts.config;
+        config.bonds_outstanding = config.bonds_outstanding.checked_sub(bonds).unwrap();
+        let market = &mut ctx.accounts.market;
+        market.pending_resolution = false;
+        market.resolved = true;
+        market.winner_index = Some(winner_index);
+        Ok(())
+    }
+
+    /// 6. Cancel Market (Invalid outcome, every vote refundable; the creation bond is slashed whoever cancels)
+    pub fn cancel_market(ctx: Context<CancelMarket>) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        let market = &mut ctx.accounts.market;
+        require!(!market.resolved, PolybetError::AlreadyResolved);
+        require!(!market.cancelled, PolybetError::MarketCancelled);
+        require!(!market.pending_resolution, PolybetError::ResolutionPending);
+        market.cancelled = true;
+
+        // A creator cancelling their own market must not get the bond back either
+        if market.creation_bond > 0 {
+            let bond = market.creation_bond;
+            market.creation_bond = 0;
+            let market_key = ctx.accounts.market.key();
+            let seeds = &[b"market_vault".as_ref(), market_key.as_ref(), &[ctx.accounts.market.vault_bump]];
+            let signer = &[&seeds[..]];
+            token_interface::transfer_checked(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), 
+                token_interface::TransferChecked { from: ctx.accounts.market_vault.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.treasury_vault.to_account_info(), authority: ctx.accounts.market_vault.to_account_info() }, 
+                signer), bond, ctx.accounts.mint.decimals)?;
+        }
+        Ok(())
+    }
+
+    /// 7. Claim Refund (Full stake back from Market Vault)
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        require!(!ctx.accounts.config.paused && !ctx.accounts.market.paused, PolybetError::Paused);
+        require!(ctx.accounts.market.cancelled, PolybetError::MarketNotCancelled);
+        require!(!ctx.accounts.vote.claimed, PolybetError::AlreadyClaimed);
+        let amount = ctx.accounts.vote.amount;
+
+        let market_key = ctx.accounts.market.key();
+        let seeds = &[b"market_vault".as_ref(), market_key.as_ref(), &[ctx.accounts.market.vault_bump]];
+        let signer = &[&seeds[..]];
+        token_interface::transfer_checked(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.market_vault.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.user_token.to_account_info(), authority: ctx.accounts.market_vault.to_account_info() }, 
+            signer), amount, ctx.accounts.mint.decimals)?;
+
+        ctx.accounts.market.deposits = ctx.accounts.market.deposits.checked_sub(amount).unwrap();
+        ctx.accounts.vote.claimed = true;
+        Ok(())
+    }
+
+    /// Outcome Tokens (market mode enabled once every outcome has its PDA-owned mint, created in order)
+    pub fn create_outcome_mint(ctx: Context<CreateOutcomeMint>, outcome_index: u8) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        let market = &mut ctx.accounts.market;
+        require!(outcome_index == market.outcome_mints_created && outcome_index < market.outcomes_count, PolybetError::InvalidOutcomeMint);
+        market.outcome_mint_bumps[outcome_index as usize] = ctx.bumps.outcome_mint;
+        market.outcome_mints_created += 1;
+        Ok(())
+    }
+
+    /// Deposit `amount` collateral, receive `amount` of every outcome token.
+    /// Remaining accounts: `[outcome_mint, user_outcome_token]` for each outcome, in order.
+    pub fn mint_complete_set<'info>(ctx: Context<'_, '_, 'info, 'info, CompleteSet<'info>>, amount: u64) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        require!(!ctx.accounts.config.paused && !ctx.accounts.market.paused, PolybetError::Paused);
+        require!(!ctx.accounts.market.resolved && !ctx.accounts.market.cancelled, PolybetError::MarketEnded);
+        let market_key = ctx.accounts.market.key();
+        let pairs = outcome_token_pairs(&ctx.accounts.market, ctx.remaining_accounts)?;
+
+        token_interface::transfer_checked(CpiContext::new(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.user_token.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.market_vault.to_account_info(), authority: ctx.accounts.user.to_account_info() }), 
+            amount, ctx.accounts.mint.decimals)?;
+
+        for (i, (mint, holder)) in pairs.into_iter().enumerate() {
+            let seeds = &[b"outcome_mint".as_ref(), market_key.as_ref(), &[i as u8], &[ctx.accounts.market.outcome_mint_bumps[i]]];
+            let signer = &[&seeds[..]];
+            token_interface::mint_to(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), 
+                token_interface::MintTo { mint: mint.clone(), to: holder.clone(), authority: mint.clone() }, 
+                signer), amount)?;
+        }
+
+        let market = &mut ctx.accounts.market;
+        market.complete_sets = market.complete_sets.checked_add(amount).unwrap();
+        Ok(())
+    }
+
+    /// Burn `amount` of every outcome token, get `amount` collateral back. Same remaining accounts as minting.
+    pub fn redeem_complete_set<'info>(ctx: Context<'_, '_, 'info, 'info, CompleteSet<'info>>, amount: u64) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        require!(!ctx.accounts.config.paused && !ctx.accounts.market.paused, PolybetError::Paused);
+        let pairs = outcome_token_pairs(&ctx.accounts.market, ctx.remaining_accounts)?;
+
+        for (mint, holder) in pairs {
+            token_interface::burn(CpiContext::new(ctx.accounts.token_program.to_account_info(), 
+                token_interface::Burn { mint: mint.clone(), from: holder.clone(), authority: ctx.accounts.user.to_account_info() }), 
+                amount)?;
+        }
+
+        let market_key = ctx.accounts.market.key();
+        let seeds = &[b"market_vault".as_ref(), market_key.as_ref(), &[ctx.accounts.market.vault_bump]];
+        let signer = &[&seeds[..]];
+        token_interface::transfer_checked(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.market_vault.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.user_token.to_account_info(), authority: ctx.accounts.market_vault.to_account_info() }, 
+            signer), amount, ctx.accounts.mint.decimals)?;
+
+        let market = &mut ctx.accounts.market;
+        market.complete_sets = market.complete_sets.checked_sub(amount).unwrap();
+        Ok(())
+    }
+
+    /// After resolution, burn winning outcome tokens 1:1 for collateral
+    pub fn redeem_winning(ctx: Context<RedeemWinning>, amount: u64) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        require!(!ctx.accounts.config.paused && !ctx.accounts.market.paused, PolybetError::Paused);
+        let market = &ctx.accounts.market;
+        require!(market.resolved, PolybetError::MarketActive);
+        let winner = market.winner_index.unwrap();
+        require!(winner < market.outcome_mints_created, PolybetError::OutcomeTokensDisabled);
+        require_keys_eq!(ctx.accounts.winning_mint.key(), market.outcome_mint_address(&market.key(), winner)?, PolybetError::InvalidOutcomeMint);
+
+        token_interface::burn(CpiContext::new(ctx.accounts.token_program.to_account_info(), 
+            token_interface::Burn { mint: ctx.accounts.winning_mint.to_account_info(), from: ctx.accounts.user_outcome_token.to_account_info(), authority: ctx.accounts.user.to_account_info() }), 
+            amount)?;
+
+        let market_key = ctx.accounts.market.key();
+        let seeds = &[b"market_vault".as_ref(), market_key.as_ref(), &[ctx.accounts.market.vault_bump]];
+        let signer = &[&seeds[..]];
+        token_interface::transfer_checked(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.market_vault.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.user_token.to_account_info(), authority: ctx.accounts.market_vault.to_account_info() }, 
+            signer), amount, ctx.accounts.mint.decimals)?;
+
+        let market = &mut ctx.accounts.market;
+        market.complete_sets = market.complete_sets.checked_sub(amount).unwrap();
+        Ok(())
+    }
+
+    /// After cancellation, burn tokens of any outcome for 1/n collateral each (rounded down), as every
+    /// outcome token is an equal share of the complete sets backing them
+    pub fn redeem_cancelled(ctx: Context<RedeemCancelled>, outcome_index: u8, amount: u64) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        require!(!ctx.accounts.config.paused && !ctx.accounts.market.paused, PolybetError::Paused);
+        let market = &ctx.accounts.market;
+        require!(market.cancelled, PolybetError::MarketNotCancelled);
+        require!(outcome_index < market.outcome_mints_created, PolybetError::OutcomeTokensDisabled);
+        require_keys_eq!(ctx.accounts.outcome_mint.key(), market.outcome_mint_address(&market.key(), outcome_index)?, PolybetError::InvalidOutcomeMint);
+        let payout = amount / market.outcomes_count as u64;
+
+        token_interface::burn(CpiContext::new(ctx.accounts.token_program.to_account_info(), 
+            token_interface::Burn { mint: ctx.accounts.outcome_mint.to_account_info(), from: ctx.accounts.user_outcome_token.to_account_info(), authority: ctx.accounts.user.to_account_info() }), 
+            amount)?;
+
+        let market_key = ctx.accounts.market.key();
+        let seeds = &[b"market_vault".as_ref(), market_key.as_ref(), &[ctx.accounts.market.vault_bump]];
+        let signer = &[&seeds[..]];
+        token_interface::transfer_checked(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.market_vault.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.user_token.to_account_info(), authority: ctx.accounts.market_vault.to_account_info() }, 
+            signer), payout, ctx.accounts.mint.decimals)?;
+
+        let market = &mut ctx.accounts.market;
+        market.complete_sets = market.complete_sets.checked_sub(payout).unwrap();
+        Ok(())
+    }
+
+    /// Creator Fees (Paid once to the market authority after resolution)
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        require!(!ctx.accounts.config.paused && !ctx.accounts.market.paused, PolybetError::Paused);
+        require!(ctx.accounts.market.resolved, PolybetError::MarketActive);
+        require!(!ctx.accounts.market.creator_fees_claimed, PolybetError::AlreadyClaimed);
+        let amount = ctx.accounts.market.creator_fees;
+
+        let market_key = ctx.accounts.market.key();
+        let seeds = &[b"market_vault".as_ref(), market_key.as_ref(), &[ctx.accounts.market.vault_bump]];
+        let signer = &[&seeds[..]];
+        token_interface::transfer_checked(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.market_vault.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.creator_token.to_account_info(), authority: ctx.accounts.market_vault.to_account_info() }, 
+            signer), amount, ctx.accounts.mint.decimals)?;
+
+        ctx.accounts.market.creator_fees_claimed = true;
+        Ok(())
+    }
+
+    /// Creation Bond (Back to the creator once their market resolved; cancelling slashes it)
+    pub fn reclaim_creation_bond(ctx: Context<ReclaimCreationBond>) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        require!(!ctx.accounts.config.paused && !ctx.accounts.market.paused, PolybetError::Paused);
+        require!(ctx.accounts.market.resolved, PolybetError::MarketActive);
+        require!(ctx.accounts.market.creation_bond > 0, PolybetError::AlreadyClaimed);
+        let amount = ctx.accounts.market.creation_bond;
+
+        let market_key = ctx.accounts.market.key();
+        let seeds = &[b"market_vault".as_ref(), market_key.as_ref(), &[ctx.accounts.market.vault_bump]];
+        let signer = &[&seeds[..]];
+        token_interface::transfer_checked(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.market_vault.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.creator_token.to_account_info(), authority: ctx.accounts.market_vault.to_account_info() }, 
+            signer), amount, ctx.accounts.mint.decimals)?;
+
+        ctx.accounts.market.creation_bond = 0;
+        Ok(())
+    }
+
+    /// LMSR Subsidy (Whatever winners didn't need goes back to the creator once the market resolved or was cancelled)
+    pub fn reclaim_subsidy(ctx: Context<ReclaimSubsidy>) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        require!(!ctx.accounts.config.paused && !ctx.accounts.market.paused, PolybetError::Paused);
+        require!(ctx.accounts.market.resolved || ctx.accounts.market.cancelled, PolybetError::MarketActive);
+        require!(ctx.accounts.market.subsidy > 0, PolybetError::AlreadyClaimed);
+        let amount = ctx.accounts.market.refundable_subsidy(ctx.accounts.market_vault.amount).ok_or(PolybetError::MathOverflow)?;
+
+        let market_key = ctx.accounts.market.key();
+        let seeds = &[b"market_vault".as_ref(), market_key.as_ref(), &[ctx.accounts.market.vault_bump]];
+        let signer = &[&seeds[..]];
+        token_interface::transfer_checked(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.market_vault.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.creator_token.to_account_info(), authority: ctx.accounts.market_vault.to_account_info() }, 
+            signer), amount, ctx.accounts.mint.decimals)?;
+
+        ctx.accounts.market.subsidy = 0;
+        Ok(())
+    }
+
+    /// Fee Parameters (Tune economics without redeploying)
+    pub fn update_fees(ctx: Context<UpdateFees>, protocol_fee_bps: u16, creator_fee_bps: u16) -> Result<()> {
+        require!(protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS, PolybetError::FeeTooHigh);
+        require!(creator_fee_bps <= MAX_CREATOR_FEE_BPS, PolybetError::FeeTooHigh);
+        let config = &mut ctx.accounts.config;
+        config.protocol_fee_bps = protocol_fee_bps;
+        config.creator_fee_bps = creator_fee_bps;
+        Ok(())
+    }
+
+    /// Whale Gate (Minimum creator balance for `initialize_market`, 0 disables it)
+    pub fn set_min_creator_balance(ctx: Context<SetMinCreatorBalance>, min_creator_balance: u64) -> Result<()> {
+        ctx.accounts.config.min_creator_balance = min_creator_balance;
+        Ok(())
+    }
+
+    /// Creation Bond (Amount `initialize_market` escrows from the creator, 0 disables it)
+    pub fn set_creation_bond(ctx: Context<SetCreationBond>, creation_bond: u64) -> Result<()> {
+        ctx.accounts.config.creation_bond = creation_bond;
+        Ok(())
+    }
+
+    /// Rotate the token account that claims must pay the protocol fee into
+    pub fn set_fee_recipient(ctx: Context<SetFeeRecipient>) -> Result<()> {
+        ctx.accounts.config.fee_recipient = ctx.accounts.fee_recipient.key();
+        Ok(())
+    }
+
+    /// Buyback Fund (Dedicated PDA fee vault, becomes the fee recipient)
+    pub fn initialize_fee_vault(ctx: Context<InitializeFeeVault>) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        let config = &mut ctx.accounts.config;
+        config.fee_vault_bump = ctx.bumps.fee_vault;
+        config.fee_recipient = ctx.accounts.fee_vault.key();
+        Ok(())
+    }
+
+    /// Buyback & Burn (Destroys accumulated protocol fees, tallied in `total_burned`)
+    pub fn burn_protocol_fees(ctx: Context<BurnProtocolFees>, amount: u64) -> Result<()> {
         require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
-        let seeds = &[b"treasury".as_ref(), &[ctx.accounts.config.vault_bump]];
+        let seeds = &[b"fee_vault".as_ref(), &[ctx.accounts.config.fee_vault_bump]];
         let signer = &[&seeds[..]];
-        token_interface::transfer(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), 
-            token_interface::Transfer { from: ctx.accounts.treasury_vault.to_account_info(), to: ctx.accounts.destination_token.to_account_info(), authority: ctx.accounts.treasury_vault.to_account_info() }, 
+        token_interface::burn(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), 
+            token_interface::Burn { mint: ctx.accounts.mint.to_account_info(), from: ctx.accounts.fee_vault.to_account_info(), authority: ctx.accounts.fee_vault.to_account_info() }, 
             signer), amount)?;
+
+        let config = &mut ctx.accounts.config;
+        config.total_burned = config.total_burned.checked_add(amount).unwrap();
+        Ok(())
+    }
+
+    /// Role Registry (Admin grants scoped powers so ops staff never need the master key)
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, member: Pubkey) -> Result<()> {
+        let assignment = &mut ctx.accounts.assignment;
+        assignment.role = role;
+        assignment.member = member;
+        assignment.bump = ctx.bumps.assignment;
+        Ok(())
+    }
+
+    /// Closes the member's registry entry, rent back to the signer
+    pub fn revoke_role(_ctx: Context<RevokeRole>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Authority Handover (Step 1: current authority nominates the next, e.g. a multisig)
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.config.pending_authority = Some(new_authority);
+        Ok(())
+    }
+
+    /// Authority Handover (Step 2: nominee signs to take over)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.new_authority.key();
+        config.pending_authority = None;
+        Ok(())
+    }
+
+    /// Same handover for a market's creator key (resolution and creator fees)
+    pub fn propose_market_authority(ctx: Context<ProposeMarketAuthority>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.market.pending_authority = Some(new_authority);
+        Ok(())
+    }
+
+    pub fn accept_market_authority(ctx: Context<AcceptMarketAuthority>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        market.authority = ctx.accounts.new_authority.key();
+        market.pending_authority = None;
+        Ok(())
+    }
+
+    /// 8. Pause / Unpause (Incident response without redeploying)
+    pub fn set_market_paused(ctx: Context<SetMarketPaused>, paused: bool) -> Result<()> {
+        ctx.accounts.market.paused = paused;
+        Ok(())
+    }
+
+    pub fn set_protocol_paused(ctx: Context<SetProtocolPaused>, paused: bool) -> Result<()> {
+        ctx.accounts.config.paused = paused;
+        Ok(())
+    }
+
+    /// Sweep Treasury (Only what is left above the resolution bonds it holds)
+    pub fn sweep_profit(ctx: Context<SweepProfit>, amount: u64) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        require!(!ctx.accounts.config.paused, PolybetError::Paused);
+        let remaining = ctx.accounts.treasury_vault.amount.checked_sub(amount).ok_or(PolybetError::InsufficientSurplus)?;
+        require!(remaining >= ctx.accounts.config.bonds_outstanding, PolybetError::InsufficientSurplus);
+        let seeds = &[b"vault".as_ref(), &[ctx.accounts.config.vault_bump]];
+        let signer = &[&seeds[..]];
+        token_interface::transfer_checked(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.treasury_vault.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.destination_token.to_account_info(), authority: ctx.accounts.treasury_vault.to_account_info() }, 
+            signer), amount, ctx.accounts.mint.decimals)?;
+        Ok(())
+    }
+
+    /// Sweep Market Profit (Losing stakes left in a market vault above everything it still owes)
+    pub fn sweep_market_profit(ctx: Context<SweepMarketProfit>, amount: u64) -> Result<()> {
+        require_keys_eq!(ctx.accounts.token_program.key(), TOKEN_2022_ID, PolybetError::InvalidProgramId);
+        require!(!ctx.accounts.config.paused && !ctx.accounts.market.paused, PolybetError::Paused);
+        let liabilities = ctx.accounts.market.liabilities().ok_or(PolybetError::MathOverflow)?;
+        let remaining = ctx.accounts.market_vault.amount.checked_sub(amount).ok_or(PolybetError::InsufficientSurplus)?;
+        require!(remaining >= liabilities, PolybetError::InsufficientSurplus);
+
+        let market_key = ctx.accounts.market.key();
+        let seeds = &[b"market_vault".as_ref(), market_key.as_ref(), &[ctx.accounts.market.vault_bump]];
+        let signer = &[&seeds[..]];
+        token_interface::transfer_checked(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), 
+            token_interface::TransferChecked { from: ctx.accounts.market_vault.to_account_info(), mint: ctx.accounts.mint.to_account_info(), to: ctx.accounts.destination_token.to_account_info(), authority: ctx.accounts.market_vault.to_account_info() }, 
+            signer), amount, ctx.accounts.mint.decimals)?;
         Ok(())
     }
 }
@@ -139,6 +802,8 @@ pub struct InitializeProtocol<'info> {
     #[account(init, payer = authority, token::token_program = token_program, token::mint = mint, token::authority = treasury_vault, seeds = [b"vault"], bump)]
     pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
     pub mint: InterfaceAccount<'info, Mint>,
+    #[account(constraint = fee_recipient.mint == mint.key() @ PolybetError::InvalidFeeRecipient)]
+    pub fee_recipient: InterfaceAccount<'info, TokenAccount>,
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -151,23 +816,64 @@ pub struct InitializeProtocol<'info> {
 #[instruction(question: String, end_timestamp: i64, outcomes_count: u8, virtual_liquidity: u64)]
 pub struct InitializeMarket<'info> {
     #[account(init, payer = authority, space = Market::SPACE, seeds = [b"market", authority.key().as_ref(), question.as_bytes()], bump)]
+    pub market: Box<Account<'info, Market>>,
+    /// Escrow for this market's bets and payouts, so one market can't drain another
+    #[account(init, payer = authority, token::token_program = token_program, token::mint = mint, token::authority = market_vault, seeds = [b"market_vault", market.key().as_ref()], bump)]
+    pub market_vault: InterfaceAccount<'info, TokenAccount>,
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// Whale gate: must hold at least `config.min_creator_balance`, pays `config.creation_bond`
+    #[account(mut, token::mint = mint, token::authority = authority)]
+    pub creator_token: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct IndexMarket<'info> {
+    /// Only the protocol curates the lookup, so a creator can't squat a Polymarket ID
+    #[account(constraint = authority.key() == config.authority || role.is_some() @ PolybetError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
     pub market: Account<'info, Market>,
+    #[account(init, payer = authority, space = MarketIndex::SPACE, seeds = [b"polymarket", hash(market.polymarket_id.as_bytes()).as_ref()], bump)]
+    pub market_index: Account<'info, MarketIndex>,
     #[account(mut)]
     pub authority: Signer<'info>,
+    #[account(seeds = [b"role", &[Role::Admin as u8], authority.key().as_ref()], bump = role.bump)]
+    pub role: Option<Account<'info, RoleAssignment>>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
+pub struct ReindexMarket<'info> {
+    #[account(constraint = authority.key() == config.authority || role.is_some() @ PolybetError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub market: Account<'info, Market>,
+    #[account(mut, seeds = [b"polymarket", hash(market.polymarket_id.as_bytes()).as_ref()], bump = market_index.bump)]
+    pub market_index: Account<'info, MarketIndex>,
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"role", &[Role::Admin as u8], authority.key().as_ref()], bump = role.bump)]
+    pub role: Option<Account<'info, RoleAssignment>>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome_index: u8, amount: u64, nonce: u64)]
 pub struct PlaceVote<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
-    #[account(init, payer = user, space = Vote::SPACE, seeds = [b"vote", market.key().as_ref(), user.key().as_ref()], bump)]
+    #[account(init, payer = user, space = Vote::SPACE, seeds = [b"vote", market.key().as_ref(), user.key().as_ref(), nonce.to_le_bytes().as_ref()], bump)]
     pub vote: Account<'info, Vote>,
-    #[account(mut, seeds = [b"vault"], bump = config.vault_bump)]
-    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
-    #[account(mut)]
+    #[account(mut, seeds = [b"market_vault", market.key().as_ref()], bump = market.vault_bump)]
+    pub market_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::authority = user)]
     pub user_token: InterfaceAccount<'info, TokenAccount>,
     pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
     #[account(mut)]
     pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -176,39 +882,492 @@ pub struct PlaceVote<'info> {
 }
 
 #[derive(Accounts)]
-pub struct ClaimWinnings<'info> {
+pub struct CashOut<'info> {
     pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
     pub market: Account<'info, Market>,
-    #[account(mut, seeds = [b"vote", market.key().as_ref(), user.key().as_ref()], bump = vote.bump, has_one = user)]
+    #[account(mut, seeds = [b"vote", market.key().as_ref(), user.key().as_ref(), vote.nonce.to_le_bytes().as_ref()], bump = vote.bump, has_one = user, close = user)]
     pub vote: Account<'info, Vote>,
-    #[account(mut, seeds = [b"vault"], bump = config.vault_bump)]
-    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
-    #[account(mut)]
+    #[account(mut, seeds = [b"market_vault", market.key().as_ref()], bump = market.vault_bump)]
+    pub market_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::authority = user)]
     pub user_token: InterfaceAccount<'info, TokenAccount>,
     #[account(mut)]
-    pub dev_token: InterfaceAccount<'info, TokenAccount>,
-    #[account(mut)]
     pub user: Signer<'info>,
     /// CHECK: Manual validation to bypass casing bugs in library constants
     pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-pub struct ResolveMarket<'info> {
+pub struct ClaimWinnings<'info> {
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(mut, seeds = [b"vote", market.key().as_ref(), user.key().as_ref(), vote.nonce.to_le_bytes().as_ref()], bump = vote.bump, has_one = user)]
+    pub vote: Account<'info, Vote>,
+    #[account(mut, seeds = [b"market_vault", market.key().as_ref()], bump = market.vault_bump)]
+    pub market_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::authority = user)]
+    pub user_token: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = config.fee_recipient @ PolybetError::InvalidFeeRecipient)]
+    pub dev_token: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveMarket<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == market.authority || role.is_some() @ PolybetError::Unauthorized,
+        constraint = market.kind != MarketKind::Optimistic @ PolybetError::OptimisticMarket,
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"role", &[Role::Resolver as u8], authority.key().as_ref()], bump = role.bump)]
+    pub role: Option<Account<'info, RoleAssignment>>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveViaOracle<'info> {
+    #[account(mut, has_one = oracle, constraint = market.kind != MarketKind::Optimistic @ PolybetError::OptimisticMarket)]
+    pub market: Account<'info, Market>,
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveWithPriceFeed<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    /// CHECK: Any receiver-owned update for the market's feed; layout, verification and feed ID checked in `pyth`
+    #[account(owner = pyth::PYTH_RECEIVER_ID @ PolybetError::InvalidPriceFeed)]
+    pub price_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeResolution<'info> {
+    #[account(mut)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, constraint = market.kind == MarketKind::Optimistic @ PolybetError::NotOptimisticMarket)]
+    pub market: Account<'info, Market>,
+    #[account(init, payer = proposer, space = Resolution::SPACE, seeds = [b"resolution", market.key().as_ref()], bump)]
+    pub resolution: Account<'info, Resolution>,
+    #[account(mut, seeds = [b"vault"], bump = config.vault_bump)]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::authority = proposer)]
+    pub proposer_token: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeResolution<'info> {
+    #[account(mut)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, seeds = [b"resolution", resolution.market.as_ref()], bump = resolution.bump)]
+    pub resolution: Account<'info, Resolution>,
+    #[account(mut, seeds = [b"vault"], bump = config.vault_bump)]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::authority = disputer)]
+    pub disputer_token: InterfaceAccount<'info, TokenAccount>,
+    pub disputer: Signer<'info>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeResolution<'info> {
+    #[account(mut)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(mut, seeds = [b"resolution", market.key().as_ref()], bump = resolution.bump, has_one = market)]
+    pub resolution: Account<'info, Resolution>,
+    #[account(mut, seeds = [b"vault"], bump = config.vault_bump)]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::authority = resolution.proposer)]
+    pub proposer_token: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SettleDispute<'info> {
+    #[account(mut, constraint = authority.key() == config.authority || role.is_some() @ PolybetError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(mut, seeds = [b"resolution", market.key().as_ref()], bump = resolution.bump, has_one = market)]
+    pub resolution: Account<'info, Resolution>,
+    #[account(mut, seeds = [b"vault"], bump = config.vault_bump)]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint)]
+    pub bond_winner_token: InterfaceAccount<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"role", &[Role::Resolver as u8], authority.key().as_ref()], bump = role.bump)]
+    pub role: Option<Account<'info, RoleAssignment>>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CancelMarket<'info> {
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, constraint = authority.key() == market.authority || authority.key() == config.authority @ PolybetError::Unauthorized)]
+    pub market: Account<'info, Market>,
+    #[account(mut, seeds = [b"market_vault", market.key().as_ref()], bump = market.vault_bump)]
+    pub market_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, seeds = [b"vault"], bump = config.vault_bump)]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(mut, seeds = [b"vote", market.key().as_ref(), user.key().as_ref(), vote.nonce.to_le_bytes().as_ref()], bump = vote.bump, has_one = user)]
+    pub vote: Account<'info, Vote>,
+    #[account(mut, seeds = [b"market_vault", market.key().as_ref()], bump = market.vault_bump)]
+    pub market_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::authority = user)]
+    pub user_token: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome_index: u8)]
+pub struct CreateOutcomeMint<'info> {
+    #[account(mut, has_one = authority)]
+    pub market: Account<'info, Market>,
+    #[account(init, payer = authority, mint::token_program = token_program, mint::decimals = collateral_mint.decimals, mint::authority = outcome_mint, seeds = [b"outcome_mint", market.key().as_ref(), &[outcome_index]], bump)]
+    pub outcome_mint: InterfaceAccount<'info, Mint>,
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteSet<'info> {
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(mut, seeds = [b"market_vault", market.key().as_ref()], bump = market.vault_bump)]
+    pub market_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::authority = user)]
+    pub user_token: InterfaceAccount<'info, TokenAccount>,
+    pub user: Signer<'info>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemWinning<'info> {
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(mut)]
+    pub winning_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, token::mint = winning_mint, token::authority = user)]
+    pub user_outcome_token: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, seeds = [b"market_vault", market.key().as_ref()], bump = market.vault_bump)]
+    pub market_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::authority = user)]
+    pub user_token: InterfaceAccount<'info, TokenAccount>,
+    pub user: Signer<'info>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemCancelled<'info> {
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(mut)]
+    pub outcome_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, token::mint = outcome_mint, token::authority = user)]
+    pub user_outcome_token: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, seeds = [b"market_vault", market.key().as_ref()], bump = market.vault_bump)]
+    pub market_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::authority = user)]
+    pub user_token: InterfaceAccount<'info, TokenAccount>,
+    pub user: Signer<'info>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Checks the `[outcome_mint, holder_token]` remaining accounts against the market's outcome mints
+fn outcome_token_pairs<'a, 'info>(market: &Account<'info, Market>, remaining: &'a [AccountInfo<'info>]) -> Result<Vec<(&'a AccountInfo<'info>, &'a AccountInfo<'info>)>> {
+    require!(market.outcomes_count > 0 && market.outcome_mints_created == market.outcomes_count, PolybetError::OutcomeTokensDisabled);
+    require!(remaining.len() == 2 * market.outcomes_count as usize, PolybetError::InvalidOutcomeMint);
+    remaining.chunks(2).enumerate().map(|(i, pair)| {
+        require_keys_eq!(pair[0].key(), market.outcome_mint_address(&market.key(), i as u8)?, PolybetError::InvalidOutcomeMint);
+        Ok((&pair[0], &pair[1]))
+    }).collect()
+}
+
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, has_one = authority)]
+    pub market: Account<'info, Market>,
+    #[account(mut, seeds = [b"market_vault", market.key().as_ref()], bump = market.vault_bump)]
+    pub market_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::authority = authority)]
+    pub creator_token: InterfaceAccount<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimCreationBond<'info> {
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, has_one = authority)]
+    pub market: Account<'info, Market>,
+    #[account(mut, seeds = [b"market_vault", market.key().as_ref()], bump = market.vault_bump)]
+    pub market_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::authority = authority)]
+    pub creator_token: InterfaceAccount<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimSubsidy<'info> {
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, has_one = authority)]
+    pub market: Account<'info, Market>,
+    #[account(mut, seeds = [b"market_vault", market.key().as_ref()], bump = market.vault_bump)]
+    pub market_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::authority = authority)]
+    pub creator_token: InterfaceAccount<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFees<'info> {
+    #[account(mut, constraint = authority.key() == config.authority || role.is_some() @ PolybetError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"role", &[Role::Admin as u8], authority.key().as_ref()], bump = role.bump)]
+    pub role: Option<Account<'info, RoleAssignment>>,
+}
+
+#[derive(Accounts)]
+pub struct SetMinCreatorBalance<'info> {
+    #[account(mut, constraint = authority.key() == config.authority || role.is_some() @ PolybetError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"role", &[Role::Admin as u8], authority.key().as_ref()], bump = role.bump)]
+    pub role: Option<Account<'info, RoleAssignment>>,
+}
+
+#[derive(Accounts)]
+pub struct SetCreationBond<'info> {
+    #[account(mut, constraint = authority.key() == config.authority || role.is_some() @ PolybetError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"role", &[Role::Admin as u8], authority.key().as_ref()], bump = role.bump)]
+    pub role: Option<Account<'info, RoleAssignment>>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeRecipient<'info> {
+    #[account(mut, constraint = authority.key() == config.authority || role.is_some() @ PolybetError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(constraint = fee_recipient.mint == config.mint @ PolybetError::InvalidFeeRecipient)]
+    pub fee_recipient: InterfaceAccount<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"role", &[Role::Admin as u8], authority.key().as_ref()], bump = role.bump)]
+    pub role: Option<Account<'info, RoleAssignment>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeeVault<'info> {
+    #[account(mut, constraint = authority.key() == config.authority || role.is_some() @ PolybetError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(init, payer = authority, token::token_program = token_program, token::mint = mint, token::authority = fee_vault, seeds = [b"fee_vault"], bump)]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"role", &[Role::Admin as u8], authority.key().as_ref()], bump = role.bump)]
+    pub role: Option<Account<'info, RoleAssignment>>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct BurnProtocolFees<'info> {
+    #[account(mut, constraint = authority.key() == config.authority || role.is_some() @ PolybetError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(mut, address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, seeds = [b"fee_vault"], bump = config.fee_vault_bump)]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"role", &[Role::Treasurer as u8], authority.key().as_ref()], bump = role.bump)]
+    pub role: Option<Account<'info, RoleAssignment>>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(role: Role, member: Pubkey)]
+pub struct GrantRole<'info> {
+    #[account(constraint = authority.key() == config.authority || role_admin.is_some() @ PolybetError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(init, payer = authority, space = RoleAssignment::SPACE, seeds = [b"role".as_ref(), &[role as u8], member.as_ref()], bump)]
+    pub assignment: Account<'info, RoleAssignment>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"role", &[Role::Admin as u8], authority.key().as_ref()], bump = role_admin.bump)]
+    pub role_admin: Option<Account<'info, RoleAssignment>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(constraint = authority.key() == config.authority || role.is_some() @ PolybetError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(mut, seeds = [b"role", &[assignment.role as u8], assignment.member.as_ref()], bump = assignment.bump, close = authority)]
+    pub assignment: Account<'info, RoleAssignment>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"role", &[Role::Admin as u8], authority.key().as_ref()], bump = role.bump)]
+    pub role: Option<Account<'info, RoleAssignment>>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(mut, has_one = authority)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(mut, constraint = config.pending_authority == Some(new_authority.key()) @ PolybetError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeMarketAuthority<'info> {
     #[account(mut, has_one = authority)]
     pub market: Account<'info, Market>,
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct AcceptMarketAuthority<'info> {
+    #[account(mut, constraint = market.pending_authority == Some(new_authority.key()) @ PolybetError::Unauthorized)]
+    pub market: Account<'info, Market>,
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMarketPaused<'info> {
+    #[account(constraint = authority.key() == config.authority || role.is_some() @ PolybetError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"role", &[Role::Pauser as u8], authority.key().as_ref()], bump = role.bump)]
+    pub role: Option<Account<'info, RoleAssignment>>,
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolPaused<'info> {
+    #[account(mut, constraint = authority.key() == config.authority || role.is_some() @ PolybetError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"role", &[Role::Pauser as u8], authority.key().as_ref()], bump = role.bump)]
+    pub role: Option<Account<'info, RoleAssignment>>,
+}
+
 #[derive(Accounts)]
 pub struct SweepProfit<'info> {
-    #[account(has_one = authority)]
+    #[account(constraint = authority.key() == config.authority || role.is_some() @ PolybetError::Unauthorized)]
     pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
     #[account(mut, seeds = [b"vault"], bump = config.vault_bump)]
     pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
-    #[account(mut)]
+    #[account(mut, token::mint = mint)]
     pub destination_token: InterfaceAccount<'info, TokenAccount>,
     pub authority: Signer<'info>,
+    #[account(seeds = [b"role", &[Role::Treasurer as u8], authority.key().as_ref()], bump = role.bump)]
+    pub role: Option<Account<'info, RoleAssignment>>,
+    /// CHECK: Manual validation to bypass casing bugs in library constants
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SweepMarketProfit<'info> {
+    #[account(constraint = authority.key() == config.authority || role.is_some() @ PolybetError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub market: Account<'info, Market>,
+    #[account(mut, seeds = [b"market_vault", market.key().as_ref()], bump = market.vault_bump)]
+    pub market_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"role", &[Role::Treasurer as u8], authority.key().as_ref()], bump = role.bump)]
+    pub role: Option<Account<'info, RoleAssignment>>,
     /// CHECK: Manual validation to bypass casing bugs in library constants
     pub token_program: Interface<'info, TokenInterface>,
 }
@@ -216,20 +1375,148 @@ pub struct SweepProfit<'info> {
 #[account]
 pub struct ProtocolConfig {
     pub authority: Pubkey,
+    /// Nominated by `propose_authority`, takes over once it signs `accept_authority`
+    pub pending_authority: Option<Pubkey>,
+    /// Collateral mint every vault and user token account must hold
+    pub mint: Pubkey,
     pub vault_bump: u8,
+    pub resolution_bond: u64,
+    pub dispute_window: i64,
+    pub protocol_fee_bps: u16,
+    pub creator_fee_bps: u16,
+    /// Token account that receives the protocol fee
+    pub fee_recipient: Pubkey,
+    /// Resolution bonds currently held in the treasury, never sweepable
+    pub bonds_outstanding: u64,
+    pub fee_vault_bump: u8,
+    /// Cumulative protocol fees destroyed by `burn_protocol_fees`
+    pub total_burned: u64,
+    /// Collateral balance a wallet needs to create markets (the $POLYBET whale gate)
+    pub min_creator_balance: u64,
+    /// Bond escrowed per market by `initialize_market`
+    pub creation_bond: u64,
+    pub paused: bool,
     pub bump: u8,
 }
 
+/// Scoped powers granted through `grant_role`. The protocol authority holds all of them implicitly.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    /// Manages roles and fee settings
+    Admin,
+    /// Resolves any market and settles disputes
+    Resolver,
+    /// Pauses markets or the whole protocol
+    Pauser,
+    /// Sweeps surplus and burns protocol fees
+    Treasurer,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Comparison {
+    Above,
+    Below,
+}
+
+/// How bets are priced. LMSR caps the market maker's loss at `b * ln(outcomes_count)`;
+/// pari-mutuel pays winners pro rata from final totals, so the market is always exactly solvent.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PricingMode {
+    FixedOdds,
+    Lmsr { b: u64 },
+    Parimutuel,
+}
+
+impl PricingMode { pub const SPACE: usize = 1 + 8; }
+
+/// How a market gets resolved. Price threshold markets settle YES (0) / NO (1) from a Pyth feed,
+/// identified by feed ID so an update posted from the settlement time can be used whenever someone resolves.
+/// Optimistic markets only settle through a bonded proposal and its dispute window.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MarketKind {
+    Standard,
+    PriceThreshold {
+        feed_id: [u8; 32],
+        strike: i64,
+        expo: i32,
+        comparison: Comparison,
+        settlement_timestamp: i64,
+    },
+    Optimistic,
+}
+
+impl MarketKind { pub const SPACE: usize = 1 + 32 + 8 + 4 + 1 + 8; }
+
 #[account]
 pub struct Market {
     pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+    pub oracle: Pubkey,
     pub question: String,
     pub end_timestamp: i64,
     pub outcomes_count: u8,
+    pub outcome_names: Vec<String>,
     pub total_pot: u64,
     pub outcome_totals: [u64; 8],
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub polymarket_id: String,
+    pub metadata_url: String,
+    pub kind: MarketKind,
+    pub pricing: PricingMode,
+    /// Outstanding LMSR shares per outcome (unused for fixed odds)
+    pub shares: [u64; 8],
+    pub outcome_mints_created: u8,
+    pub outcome_mint_bumps: [u8; 8],
+    /// Collateral backing outstanding outcome tokens
+    pub complete_sets: u64,
+    /// Creator's share of bets, claimable by `authority` once resolved
+    pub creator_fees: u64,
+    pub creator_fees_claimed: bool,
+    /// Creator's bond held in the market vault until reclaimed or slashed
+    pub creation_bond: u64,
+    /// LMSR market maker subsidy (`lmsr::max_loss`) paid in by the creator, 0 for other pricing
+    pub subsidy: u64,
+    /// `config.protocol_fee_bps` when the market opened, taken from the pari-mutuel pool at claim time
+    pub protocol_fee_bps: u16,
+    /// Full amounts of open votes, owed back if the market is cancelled
+    pub deposits: u64,
+    /// Per outcome, what open votes would be paid if it wins (see `Vote::owed`)
+    pub payouts_owed: [u64; 8],
     pub resolved: bool,
     pub winner_index: Option<u8>,
+    pub cancelled: bool,
+    pub pending_resolution: bool,
+    pub paused: bool,
+    pub vault_bump: u8,
+    pub bump: u8,
+}
+
+/// Resolves a Polymarket ID (hashed, as IDs can exceed the 32-byte seed limit) to its market
+#[account]
+pub struct MarketIndex {
+    pub market: Pubkey,
+    pub bump: u8,
+}
+
+/// Optimistic resolution proposal for a market, bonds held in the Global Vault
+#[account]
+pub struct Resolution {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    pub proposed_index: u8,
+    pub disputer: Option<Pubkey>,
+    pub bond: u64,
+    pub dispute_deadline: i64,
+    pub settled: bool,
+    pub bump: u8,
+}
+
+/// Registry entry granting `role` to `member`, at `[b"role", role, member]`
+#[account]
+pub struct RoleAssignment {
+    pub role: Role,
+    pub member: Pubkey,
     pub bump: u8,
 }
 
@@ -238,6 +1525,7 @@ pub struct Vote {
     pub user: Pubkey,
     pub market: Pubkey,
     pub outcome_index: u8,
+    pub nonce: u64,
     pub amount: u64,
     pub locked_payout: u64,
     pub locked_creator_fee: u64,
@@ -246,9 +1534,102 @@ pub struct Vote {
     pub bump: u8,
 }
 
-impl ProtocolConfig { pub const SPACE: usize = 8 + 32 + 1 + 1; }
-impl Market { pub const SPACE: usize = 8 + 32 + (4 + 64) + 8 + 1 + 8 + 64 + 1 + 2 + 1; }
-impl Vote { pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 1 + 1; }
+impl ProtocolConfig { pub const SPACE: usize = 8 + 32 + 33 + 32 + 1 + 8 + 8 + 2 + 2 + 32 + 8 + 1 + 8 + 8 + 8 + 1 + 1; }
+impl Market {
+    /// Size of every per-outcome array
+    pub const MAX_OUTCOMES: u8 = 8;
+    pub const MAX_OUTCOME_NAME_LEN: usize = 32;
+    /// The question is a PDA seed, so it is bounded by the 32-byte seed limit (well inside its 64 bytes of space)
+    pub const MAX_QUESTION_LEN: usize = 32;
+    pub const MAX_POLYMARKET_ID_LEN: usize = 66;
+    pub const MAX_METADATA_URL_LEN: usize = 200;
+    pub const SPACE: usize = 8 + 32 + 33 + 32 + (4 + 64) + 8 + 1 + (4 + 8 * (4 + Self::MAX_OUTCOME_NAME_LEN)) + 8 + 64 + 8 + 8
+        + (4 + Self::MAX_POLYMARKET_ID_LEN) + (4 + Self::MAX_METADATA_URL_LEN) + MarketKind::SPACE + PricingMode::SPACE + 64 + 1 + 8 + 8 + 8 + 1 + 8 + 8 + 2 + 8 + 64 + 1 + 2 + 1 + 1 + 1 + 1 + 1;
+
+    pub fn outcome_mint_address(&self, market: &Pubkey, outcome_index: u8) -> Result<Pubkey> {
+        Pubkey::create_program_address(
+            &[b"outcome_mint", market.as_ref(), &[outcome_index], &[self.outcome_mint_bumps[outcome_index as usize]]],
+            &crate::ID,
+        ).map_err(|_| error!(PolybetError::InvalidOutcomeMint))
+    }
+
+    /// Collateral the market vault still owes bettors, outcome token holders and the creator (fees, bond and subsidy).
+    /// Before resolution this covers whichever outcome wins, or a full refund if it is cancelled.
+    pub fn liabilities(&self) -> Option<u64> {
+        let creator_fees = if self.creator_fees_claimed { 0 } else { self.creator_fees };
+        let bets = if self.cancelled {
+            self.deposits
+        } else if let (true, Some(winner)) = (self.resolved, self.winner_index) {
+            let owed = self.payouts_owed[winner as usize];
+            let owed = match self.pricing {
+                // Unclaimed winning stake's share of the whole pool, protocol fee included
+                PricingMode::Parimutuel if owed > 0 => (owed as u128)
+                    .checked_mul(self.total_pot as u128)?
+                    .checked_div(self.outcome_totals[winner as usize] as u128)? as u64,
+                _ => owed,
+            };
+            owed.checked_add(creator_fees)?
+        } else {
+            let worst = match self.pricing {
+                PricingMode::Parimutuel => self.total_pot,
+                _ => *self.payouts_owed[..self.outcomes_count as usize].iter().max().unwrap_or(&0),
+            };
+            self.deposits.max(worst.checked_add(creator_fees)?)
+        };
+        bets.checked_add(self.complete_sets)?.checked_add(self.creation_bond)?.checked_add(self.subsidy)
+    }
+
+    /// Part of the subsidy still in the vault once everything else it owes is set aside
+    pub fn refundable_subsidy(&self, vault_balance: u64) -> Option<u64> {
+        let others = self.liabilities()?.checked_sub(self.subsidy)?;
+        Some(vault_balance.saturating_sub(others).min(self.subsidy))
+    }
+
+    /// What an open vote is worth before the exit fee, priced as if it had never been placed
+    pub fn exit_value(&self, vote: &Vote) -> Option<u64> {
+        let i = vote.outcome_index as usize;
+        match self.pricing {
+            // Locked payout * implied probability of its outcome without this stake, the same odds it was bought at.
+            // Capped at the stake: later bets on the same side raise that probability, but their deposits back
+            // their own payouts, not this exit
+            PricingMode::FixedOdds => {
+                let backing = self.outcome_totals[i].checked_sub(vote.stake())?;
+                let pot = self.total_pot.checked_sub(vote.stake())?;
+                if backing > 0 {
+                    (vote.locked_payout as u128)
+                        .checked_mul(backing as u128)?
+                        .checked_div(pot as u128)
+                        .map(|value| (value as u64).min(vote.stake()))
+                } else {
+                    Some(vote.stake().min(vote.locked_payout))
+                }
+            }
+            // Sell the shares back along the cost curve
+            PricingMode::Lmsr { b } => {
+                let n = self.outcomes_count as usize;
+                lmsr::proceeds_for_shares(&self.shares[..n], b, i, vote.locked_payout)
+            }
+            PricingMode::Parimutuel => Some(vote.stake()),
+        }
+    }
+}
+impl MarketIndex { pub const SPACE: usize = 8 + 32 + 1; }
+impl Resolution { pub const SPACE: usize = 8 + 32 + 32 + 1 + 33 + 8 + 8 + 1 + 1; }
+impl RoleAssignment { pub const SPACE: usize = 8 + 1 + 32 + 1; }
+impl Vote {
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 1 + 1;
+
+    /// Amount that entered the pool, net of the creator's cut
+    pub fn stake(&self) -> u64 { self.amount - self.locked_creator_fee }
+
+    /// What the market vault pays out if this vote wins: payout plus protocol fee, or the stake for pari-mutuel
+    pub fn owed(&self, pricing: &PricingMode) -> u64 {
+        match pricing {
+            PricingMode::Parimutuel => self.stake(),
+            _ => self.locked_payout + self.locked_dev_fee,
+        }
+    }
+}
 
 #[error_code]
 pub enum PolybetError {
@@ -258,4 +1639,45 @@ pub enum PolybetError {
     #[msg("Already claimed.")] AlreadyClaimed,
     #[msg("Unauthorized.")] Unauthorized,
     #[msg("Invalid Program ID.")] InvalidProgramId,
+    #[msg("Market already resolved.")] AlreadyResolved,
+    #[msg("Market cancelled.")] MarketCancelled,
+    #[msg("Market not cancelled.")] MarketNotCancelled,
+    #[msg("Paused.")] Paused,
+    #[msg("Outcome names must match outcomes count.")] OutcomeNamesMismatch,
+    #[msg("Outcome name too long.")] OutcomeNameTooLong,
+    #[msg("Invalid bet limits.")] InvalidBetLimits,
+    #[msg("Bet below market minimum.")] BetTooSmall,
+    #[msg("Bet above market maximum.")] BetTooLarge,
+    #[msg("Polymarket ID too long.")] PolymarketIdTooLong,
+    #[msg("Metadata URL too long.")] MetadataUrlTooLong,
+    #[msg("Market has no Polymarket ID.")] MissingPolymarketId,
+    #[msg("Invalid price market.")] InvalidPriceMarket,
+    #[msg("Invalid price feed.")] InvalidPriceFeed,
+    #[msg("Settlement time not reached.")] SettlementNotReached,
+    #[msg("Stale price.")] StalePrice,
+    #[msg("Price confidence too wide.")] PriceTooUncertain,
+    #[msg("Dispute window must be positive.")] InvalidDisputeWindow,
+    #[msg("Resolution pending.")] ResolutionPending,
+    #[msg("Resolution already settled.")] ResolutionSettled,
+    #[msg("Resolution already disputed.")] AlreadyDisputed,
+    #[msg("Resolution not disputed.")] NotDisputed,
+    #[msg("Dispute window closed.")] DisputeWindowClosed,
+    #[msg("Dispute window still open.")] DisputeWindowOpen,
+    #[msg("Invalid LMSR liquidity parameter.")] InvalidLiquidityParameter,
+    #[msg("Math overflow.")] MathOverflow,
+    #[msg("Invalid outcome mint.")] InvalidOutcomeMint,
+    #[msg("Outcome tokens not enabled for this market.")] OutcomeTokensDisabled,
+    #[msg("Fee above allowed maximum.")] FeeTooHigh,
+    #[msg("Fee account does not match the configured recipient.")] InvalidFeeRecipient,
+    #[msg("Sweep would leave the vault below its liabilities.")] InsufficientSurplus,
+    #[msg("Creator balance below the market creation minimum.")] CreatorBalanceTooLow,
+    #[msg("Markets need between 2 and 8 outcomes.")] InvalidOutcomesCount,
+    #[msg("End timestamp must be in the future.")] EndTimestampInPast,
+    #[msg("Outcome weights overflow or leave liquidity unassigned.")] InvalidWeights,
+    #[msg("Winner index out of range.")] InvalidWinnerIndex,
+    #[msg("Market resolves through a bonded proposal.")] OptimisticMarket,
+    #[msg("Resolution bond must be positive.")] InvalidResolutionBond,
+    #[msg("Outcome index out of range.")] InvalidOutcomeIndex,
+    #[msg("Question must be 1 to 32 bytes.")] InvalidQuestion,
+    #[msg("Only optimistic markets resolve through a bonded proposal.")] NotOptimisticMarket,
 }
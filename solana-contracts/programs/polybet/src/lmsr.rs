@@ -0,0 +1,132 @@
+//! Fixed-point LMSR (logarithmic market scoring rule).
+//!
+//! Cost function `C(q) = b * ln(sum(exp(q_j / b)))`, evaluated in 1e18 fixed point.
+//! Each outstanding share pays one base unit if its outcome wins, so the market maker
+//! can never lose more than `b * ln(n)` across all trades.
+
+/// 1.0 in fixed point
+pub const WAD: i128 = 1_000_000_000_000_000_000;
+const LN2_WAD: i128 = 693_147_180_559_945_309;
+/// exp() arguments outside this range either underflow to 0 or overflow u128
+const EXP_MIN: i128 = -42 * WAD;
+const EXP_MAX: i128 = 40 * WAD;
+
+/// e^x for x in fixed point
+pub fn exp_wad(x: i128) -> Option<i128> {
+    if x < EXP_MIN {
+        return Some(0);
+    }
+    if x > EXP_MAX {
+        return None;
+    }
+
+    // x = k * ln2 + r with |r| <= ln2 / 2, so e^x = 2^k * e^r
+    let k = (x + x.signum() * LN2_WAD / 2) / LN2_WAD;
+    let r = x - k * LN2_WAD;
+
+    let mut term = WAD;
+    let mut sum = WAD;
+    for n in 1..=30 {
+        term = term * r / (n * WAD);
+        if term == 0 {
+            break;
+        }
+        sum += term;
+    }
+
+    Some(if k >= 0 { sum << k } else { sum >> -k })
+}
+
+/// ln(x) for x > 0 in fixed point
+pub fn ln_wad(x: i128) -> Option<i128> {
+    if x <= 0 {
+        return None;
+    }
+
+    // x = 2^k * y with y in [1, 2)
+    let mut k: i128 = 0;
+    let mut y = x;
+    while y >= 2 * WAD {
+        y >>= 1;
+        k += 1;
+    }
+    while y < WAD {
+        y <<= 1;
+        k -= 1;
+    }
+
+    // ln(y) = 2 * atanh(z), z = (y - 1) / (y + 1) in [0, 1/3)
+    let z = (y - WAD) * WAD / (y + WAD);
+    let z2 = z * z / WAD;
+    let mut term = z;
+    let mut sum = z;
+    let mut n = 1;
+    while term != 0 {
+        term = term * z2 / WAD;
+        sum += term / (2 * n + 1);
+        n += 1;
+    }
+
+    Some(k * LN2_WAD + 2 * sum)
+}
+
+/// `sum(exp((q_j - max) / b))` and `max`, shifted so the largest term is exactly 1.0
+fn normalized_sum(shares: &[u64], b: u64) -> Option<(i128, i128)> {
+    let max = *shares.iter().max()? as i128;
+    let mut sum = 0i128;
+    for q in shares {
+        sum = sum.checked_add(exp_wad(-(max - *q as i128) * WAD / b as i128)?)?;
+    }
+    Some((sum, max))
+}
+
+/// C(q) in fixed point
+pub fn cost(shares: &[u64], b: u64) -> Option<i128> {
+    if b == 0 {
+        return None;
+    }
+    let (sum, max) = normalized_sum(shares, b)?;
+    max.checked_mul(WAD)?.checked_add((b as i128).checked_mul(ln_wad(sum)?)?)
+}
+
+/// Shares of `outcome` bought for exactly `amount` (rounded down in the market's favour)
+pub fn shares_for_cost(shares: &[u64], b: u64, outcome: usize, amount: u64) -> Option<u64> {
+    if b == 0 || outcome >= shares.len() {
+        return None;
+    }
+    let (sum, max) = normalized_sum(shares, b)?;
+    let target = exp_wad(-(max - shares[outcome] as i128) * WAD / b as i128)?;
+
+    // Solve C(q + d * e_i) = C(q) + amount, with a = amount / b:
+    // exp((q_i + d - max) / b) = sum * (exp(a) - 1) + exp((q_i - max) / b)
+    // Factoring out exp(a) keeps the logarithm's argument below n however large the bet:
+    // d = max - q_i + amount + b * ln(sum * (1 - exp(-a)) + exp((q_i - max) / b) * exp(-a))
+    let decay = exp_wad(-(amount as i128).checked_mul(WAD)? / b as i128)?;
+    let x = sum.checked_mul(WAD - decay)? / WAD + target.checked_mul(decay)? / WAD;
+    let d = (max - shares[outcome] as i128 + amount as i128)
+        .checked_mul(WAD)?
+        .checked_add((b as i128).checked_mul(ln_wad(x)?)?)?
+        / WAD;
+
+    u64::try_from(d.max(0)).ok()
+}
+
+/// Collateral returned for selling `amount` shares of `outcome` (rounded down in the market's favour)
+pub fn proceeds_for_shares(shares: &[u64], b: u64, outcome: usize, amount: u64) -> Option<u64> {
+    if outcome >= shares.len() {
+        return None;
+    }
+    let before = cost(shares, b)?;
+    let mut after_shares = [0u64; 8];
+    after_shares[..shares.len()].copy_from_slice(shares);
+    after_shares[outcome] = after_shares[outcome].checked_sub(amount)?;
+    let after = cost(&after_shares[..shares.len()], b)?;
+
+    u64::try_from((before - after).max(0) / WAD).ok()
+}
+
+/// Worst-case market maker loss `b * ln(n)`, rounded up: the subsidy a market needs
+pub fn max_loss(b: u64, outcomes: u8) -> Option<u64> {
+    let ln_n = ln_wad((outcomes as i128).checked_mul(WAD)?)?;
+    u64::try_from(((b as i128).checked_mul(ln_n)? + WAD - 1) / WAD).ok()
+}
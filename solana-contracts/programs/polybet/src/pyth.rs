@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey;
+
+use crate::{Comparison, PolybetError};
+
+/// Pyth Solana Receiver (owner of every `PriceUpdateV2` account, push feeds and posted pull updates alike)
+pub const PYTH_RECEIVER_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// sha256("account:PriceUpdateV2")[..8]
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Max seconds between the settlement timestamp and the price used to settle
+pub const MAX_PRICE_AGE: i64 = 60;
+/// Max confidence interval as a share of price (1%)
+pub const MAX_CONF_BPS: u64 = 100;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PriceFeedMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+/// Mirror of the receiver's `PriceUpdateV2` layout (pulling in the Pyth SDK conflicts with solana-program 1.18)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PriceUpdateV2 {
+    pub write_authority: Pubkey,
+    pub verification_level: VerificationLevel,
+    pub price_message: PriceFeedMessage,
+    pub posted_slot: u64,
+}
+
+impl PriceUpdateV2 {
+    pub fn try_from_slice_checked(data: &[u8]) -> Result<Self> {
+        require!(data.len() > 8 && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR, PolybetError::InvalidPriceFeed);
+        let mut body = &data[8..];
+        let update = Self::deserialize(&mut body).map_err(|_| error!(PolybetError::InvalidPriceFeed))?;
+        require!(update.verification_level == VerificationLevel::Full, PolybetError::InvalidPriceFeed);
+        Ok(update)
+    }
+}
+
+/// Returns the winning outcome (0 = condition met, 1 = not met) for a price threshold market.
+/// Only the first update published at or after the settlement time counts, so a resolver can't
+/// pick whichever later price suits them.
+pub fn settle_threshold(
+    message: &PriceFeedMessage,
+    feed_id: &[u8; 32],
+    strike: i64,
+    expo: i32,
+    comparison: Comparison,
+    settlement_timestamp: i64,
+    now: i64,
+) -> Result<u8> {
+    require!(now >= settlement_timestamp, PolybetError::SettlementNotReached);
+    require!(message.feed_id == *feed_id, PolybetError::InvalidPriceFeed);
+    require!(message.exponent == expo, PolybetError::InvalidPriceFeed);
+    require!(
+        message.prev_publish_time < settlement_timestamp && settlement_timestamp <= message.publish_time,
+        PolybetError::StalePrice
+    );
+    require!(message.publish_time - settlement_timestamp <= MAX_PRICE_AGE, PolybetError::StalePrice);
+    require!(message.price > 0, PolybetError::InvalidPriceFeed);
+    require!(
+        (message.conf as u128) * 10_000 <= (message.price as u128) * MAX_CONF_BPS as u128,
+        PolybetError::PriceTooUncertain
+    );
+
+    let met = match comparison {
+        Comparison::Above => message.price > strike,
+        Comparison::Below => message.price < strike,
+    };
+    Ok(if met { 0 } else { 1 })
+}
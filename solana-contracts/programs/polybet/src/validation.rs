@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{lmsr, Market, MarketKind, PolybetError, PricingMode};
+
+/// `initialize_market` arguments, checked before any of them touch the market account
+pub struct MarketParams<'a> {
+    pub question: &'a str,
+    pub end_timestamp: i64,
+    pub outcomes_count: u8,
+    pub virtual_liquidity: u64,
+    pub weights: &'a [u32; 8],
+    pub outcome_names: &'a [String],
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub polymarket_id: &'a str,
+    pub metadata_url: &'a str,
+    pub kind: &'a MarketKind,
+    pub pricing: &'a PricingMode,
+}
+
+/// Rejects any market the rest of the program can't safely index, price or settle
+pub fn check_market(params: &MarketParams, now: i64) -> Result<()> {
+    let outcomes_count = params.outcomes_count;
+    require!((2..=Market::MAX_OUTCOMES).contains(&outcomes_count), PolybetError::InvalidOutcomesCount);
+    require!((1..=Market::MAX_QUESTION_LEN).contains(&params.question.len()), PolybetError::InvalidQuestion);
+    require!(params.end_timestamp > now, PolybetError::EndTimestampInPast);
+    require!(params.min_bet > 0 && params.min_bet <= params.max_bet, PolybetError::InvalidBetLimits);
+    require!(params.polymarket_id.len() <= Market::MAX_POLYMARKET_ID_LEN, PolybetError::PolymarketIdTooLong);
+    require!(params.metadata_url.len() <= Market::MAX_METADATA_URL_LEN, PolybetError::MetadataUrlTooLong);
+    require!(params.outcome_names.len() == outcomes_count as usize, PolybetError::OutcomeNamesMismatch);
+    require!(params.outcome_names.iter().all(|name| name.len() <= Market::MAX_OUTCOME_NAME_LEN), PolybetError::OutcomeNameTooLong);
+
+    // Virtual liquidity is split by weight, so it needs a non-zero total that fits in u32
+    let total_weight = params.weights[..outcomes_count as usize]
+        .iter()
+        .try_fold(0u32, |total, weight| total.checked_add(*weight))
+        .ok_or(PolybetError::InvalidWeights)?;
+    require!(params.virtual_liquidity == 0 || total_weight > 0, PolybetError::InvalidWeights);
+
+    match *params.pricing {
+        PricingMode::Lmsr { b } => require!(lmsr::max_loss(b, outcomes_count).is_some_and(|loss| loss > 0), PolybetError::InvalidLiquidityParameter),
+        // Only real stakes may back a pari-mutuel pool
+        PricingMode::Parimutuel => require!(params.virtual_liquidity == 0, PolybetError::InvalidLiquidityParameter),
+        PricingMode::FixedOdds => {}
+    }
+    if let MarketKind::PriceThreshold { settlement_timestamp, .. } = *params.kind {
+        require!(outcomes_count == 2, PolybetError::InvalidPriceMarket);
+        require!(settlement_timestamp >= params.end_timestamp, PolybetError::InvalidPriceMarket);
+    }
+    Ok(())
+}
+
+/// Every resolution path must name one of the market's outcomes
+pub fn check_winner(outcomes_count: u8, winner_index: u8) -> Result<()> {
+    require!(winner_index < outcomes_count, PolybetError::InvalidWinnerIndex);
+    Ok(())
+}
+
+/// Votes can only back one of the market's outcomes
+pub fn check_outcome(outcomes_count: u8, outcome_index: u8) -> Result<()> {
+    require!(outcome_index < outcomes_count, PolybetError::InvalidOutcomeIndex);
+    Ok(())
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+use common::*;
+use polybet::{AcceptAuthority, AcceptMarketAuthority, PolybetError, ProposeAuthority, ProposeMarketAuthority};
+
+fn config_with_pending(admin: Pubkey, pending: Option<Pubkey>) -> AccountInfo<'static> {
+    let mut config = config(admin, Pubkey::new_unique(), Pubkey::new_unique());
+    config.pending_authority = pending;
+    program_account(pda(&[b"config"]).0, &config)
+}
+
+fn market_with_pending(creator: Pubkey, pending: Option<Pubkey>) -> AccountInfo<'static> {
+    let key = Pubkey::new_unique();
+    let mut market = market(key, creator);
+    market.pending_authority = pending;
+    program_account(key, &market)
+}
+
+#[test]
+fn only_authority_can_propose() {
+    let admin = Pubkey::new_unique();
+    assert!(validate::<ProposeAuthority>(vec![config_with_pending(admin, None), signer(admin)]).is_ok());
+
+    let err = validate::<ProposeAuthority>(vec![config_with_pending(admin, None), signer(Pubkey::new_unique())]).unwrap_err();
+    assert_eq!(err, error!(ErrorCode::ConstraintHasOne));
+}
+
+#[test]
+fn nominee_accepts() {
+    let next = Pubkey::new_unique();
+    let config = config_with_pending(Pubkey::new_unique(), Some(next));
+    assert!(validate::<AcceptAuthority>(vec![config, signer(next)]).is_ok());
+}
+
+#[test]
+fn accept_rejects_anyone_else() {
+    let admin = Pubkey::new_unique();
+    let config = config_with_pending(admin, Some(Pubkey::new_unique()));
+    let err = validate::<AcceptAuthority>(vec![config, signer(admin)]).unwrap_err();
+    assert_eq!(err, error!(PolybetError::Unauthorized));
+}
+
+#[test]
+fn accept_rejects_without_nomination() {
+    let next = Pubkey::new_unique();
+    let err = validate::<AcceptAuthority>(vec![config_with_pending(Pubkey::new_unique(), None), signer(next)]).unwrap_err();
+    assert_eq!(err, error!(PolybetError::Unauthorized));
+}
+
+#[test]
+fn market_authority_handover() {
+    let (creator, next) = (Pubkey::new_unique(), Pubkey::new_unique());
+    assert!(validate::<ProposeMarketAuthority>(vec![market_with_pending(creator, None), signer(creator)]).is_ok());
+    let err = validate::<ProposeMarketAuthority>(vec![market_with_pending(creator, None), signer(next)]).unwrap_err();
+    assert_eq!(err, error!(ErrorCode::ConstraintHasOne));
+
+    assert!(validate::<AcceptMarketAuthority>(vec![market_with_pending(creator, Some(next)), signer(next)]).is_ok());
+    let err = validate::<AcceptMarketAuthority>(vec![market_with_pending(creator, Some(next)), signer(creator)]).unwrap_err();
+    assert_eq!(err, error!(PolybetError::Unauthorized));
+}
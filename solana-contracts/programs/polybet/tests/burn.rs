@@ -0,0 +1,44 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+use common::*;
+use polybet::{BurnProtocolFees, PolybetError};
+
+fn burn_accounts(mint: Pubkey, admin: Pubkey, fee_vault: AccountInfo<'static>, signer_key: Pubkey) -> Vec<AccountInfo<'static>> {
+    vec![
+        program_account(pda(&[b"config"]).0, &config(admin, mint, fee_vault.key())),
+        mint_account(mint, 6),
+        fee_vault,
+        signer(signer_key),
+        absent(),
+        token_program(),
+    ]
+}
+
+#[test]
+fn authority_burns_from_fee_vault() {
+    let (mint, admin) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let vault = pda(&[b"fee_vault"]).0;
+    let fee_vault = token_account(vault, mint, vault, 1_000);
+    assert!(validate::<BurnProtocolFees>(burn_accounts(mint, admin, fee_vault, admin)).is_ok());
+}
+
+#[test]
+fn burn_rejects_other_token_account() {
+    let (mint, admin) = (Pubkey::new_unique(), Pubkey::new_unique());
+    // Burning from anything but the fee vault PDA would destroy someone else's funds
+    let treasury = pda(&[b"vault"]).0;
+    let fee_vault = token_account(treasury, mint, treasury, 1_000);
+    let err = validate::<BurnProtocolFees>(burn_accounts(mint, admin, fee_vault, admin)).unwrap_err();
+    assert_eq!(err, error!(ErrorCode::ConstraintSeeds));
+}
+
+#[test]
+fn burn_rejects_non_authority() {
+    let (mint, admin) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let vault = pda(&[b"fee_vault"]).0;
+    let fee_vault = token_account(vault, mint, vault, 1_000);
+    let err = validate::<BurnProtocolFees>(burn_accounts(mint, admin, fee_vault, Pubkey::new_unique())).unwrap_err();
+    assert_eq!(err, error!(PolybetError::Unauthorized));
+}
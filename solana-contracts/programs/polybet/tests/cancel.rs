@@ -0,0 +1,54 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+use common::*;
+use polybet::PolybetError;
+
+#[test]
+fn cancel_refunds_every_vote_in_full() {
+    let f = Fixture::new();
+    let ledger = f.ledger(&f.open_market());
+    let (yes, no) = (ledger.wallet(1_000), ledger.wallet(500));
+    let yes_vote = ledger.place_vote(&yes, 0, 0, 1_000).unwrap();
+    let no_vote = ledger.place_vote(&no, 0, 1, 500).unwrap();
+
+    let err = ledger.claim_refund(&yes, &yes_vote).unwrap_err();
+    assert_eq!(err, error!(PolybetError::MarketNotCancelled).into());
+
+    ledger.cancel(&ledger.creator.signer).unwrap();
+    ledger.claim_refund(&yes, &yes_vote).unwrap();
+    ledger.claim_refund(&no, &no_vote).unwrap();
+    assert_eq!(token_amount(&yes.token), 1_000);
+    assert_eq!(token_amount(&no.token), 500);
+    assert_eq!(token_amount(&ledger.market_vault), 0);
+    assert_eq!(ledger.market().deposits, 0);
+
+    let err = ledger.claim_refund(&yes, &yes_vote).unwrap_err();
+    assert_eq!(err, error!(PolybetError::AlreadyClaimed).into());
+    assert_eq!(token_amount(&yes.token), 1_000);
+}
+
+#[test]
+fn cashed_out_vote_is_not_refunded_again() {
+    let f = Fixture::new();
+    let ledger = f.ledger(&f.open_market());
+    let (seller, holder) = (ledger.wallet(1_000), ledger.wallet(1_000));
+    let sold = ledger.place_vote(&seller, 0, 0, 1_000).unwrap();
+    let held = ledger.place_vote(&holder, 0, 1, 1_000).unwrap();
+
+    // 1,800 locked * 1,000 / 3,000 once its own stake is taken out, less the 3% exit fee
+    ledger.cash_out(&seller, &sold).unwrap();
+    assert_eq!(token_amount(&seller.token), 582);
+
+    ledger.cancel(&ledger.creator.signer).unwrap();
+    let err = ledger.claim_refund(&seller, &sold).unwrap_err();
+    assert_eq!(err, error!(ErrorCode::AccountNotInitialized).into());
+    assert_eq!(token_amount(&seller.token), 582);
+
+    ledger.claim_refund(&holder, &held).unwrap();
+    assert_eq!(token_amount(&holder.token), 1_000);
+    // Only the exit fee and the seller's discount stay behind, owed to nobody
+    assert_eq!(token_amount(&ledger.market_vault), 418);
+    assert_eq!(ledger.market().liabilities(), Some(0));
+}
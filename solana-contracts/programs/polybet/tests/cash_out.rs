@@ -0,0 +1,65 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::*;
+use polybet::{Market, PricingMode, Vote};
+
+/// 1,000 / 1,000 of virtual liquidity, then a 1,000 bet on YES locked at 1,800 + 180 (10% protocol fee)
+fn after_bet(pricing: PricingMode) -> (Market, Vote) {
+    let key = Pubkey::new_unique();
+    let mut market = market(key, Pubkey::new_unique());
+    market.resolved = false;
+    market.winner_index = None;
+    market.pricing = pricing;
+    market.total_pot = 3_000;
+    market.outcome_totals[..2].copy_from_slice(&[2_000, 1_000]);
+
+    let (_, mut vote) = vote(key, Pubkey::new_unique(), 0);
+    vote.amount = 1_000;
+    vote.locked_payout = 1_800;
+    vote.locked_dev_fee = 180;
+    (market, vote)
+}
+
+#[test]
+fn fixed_odds_exit_uses_odds_the_vote_was_bought_at() {
+    let (market, vote) = after_bet(PricingMode::FixedOdds);
+    // Pricing at post-bet odds would return 1,800 * 2/3 = 1,200 for a 1,000 stake
+    assert_eq!(market.exit_value(&vote), Some(900));
+}
+
+#[test]
+fn fixed_odds_exit_follows_other_bettors() {
+    let (mut market, vote) = after_bet(PricingMode::FixedOdds);
+    market.total_pot += 2_000;
+    market.outcome_totals[1] += 2_000;
+    assert_eq!(market.exit_value(&vote), Some(1_800 * 1_000 / 4_000));
+}
+
+#[test]
+fn fixed_odds_exit_never_exceeds_stake() {
+    let (mut market, vote) = after_bet(PricingMode::FixedOdds);
+    // Backing the same side raises the implied probability to 3/4: 1,350 uncapped
+    market.total_pot += 2_000;
+    market.outcome_totals[0] += 2_000;
+    assert_eq!(market.exit_value(&vote), Some(1_000));
+}
+
+#[test]
+fn cash_out_after_same_side_bets_leaves_their_payouts_covered() {
+    let f = Fixture::new();
+    let ledger = f.ledger(&f.open_market());
+    let (loser, early, late) = (ledger.wallet(1_000), ledger.wallet(1_000), ledger.wallet(1_000));
+    ledger.place_vote(&loser, 0, 1, 1_000).unwrap();
+    let early_vote = ledger.place_vote(&early, 0, 0, 1_000).unwrap();
+    let late_vote = ledger.place_vote(&late, 0, 0, 1_000).unwrap();
+
+    // Uncapped, the late bet lifts this exit to 2,700 * 2/4 = 1,350 and the vault can't pay the late bettor
+    ledger.cash_out(&early, &early_vote).unwrap();
+    assert_eq!(token_amount(&early.token), 970);
+
+    ledger.resolve(0).unwrap();
+    ledger.claim_winnings(&late, &late_vote).unwrap();
+    assert_eq!(token_amount(&late.token), 1_800);
+    assert_eq!(token_amount(&ledger.market_vault), 3_000 - 970 - 1_800 - 180);
+}
@@ -0,0 +1,203 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+use common::*;
+use polybet::{ClaimRefund, CompleteSet, DisputeResolution, Resolution};
+
+impl Fixture {
+    fn deposit_accounts(&self, mint: AccountInfo<'static>, user_token: AccountInfo<'static>) -> Vec<AccountInfo<'static>> {
+        vec![
+            self.config(),
+            mint,
+            self.market(),
+            self.market_vault(),
+            user_token,
+            signer(self.user),
+            token_program(),
+        ]
+    }
+
+    fn dispute_accounts(&self, disputer_token: AccountInfo<'static>) -> Vec<AccountInfo<'static>> {
+        let (key, bump) = pda(&[b"resolution", self.market.as_ref()]);
+        let resolution = Resolution {
+            market: self.market,
+            proposer: self.admin,
+            proposed_index: 0,
+            disputer: None,
+            bond: 100,
+            dispute_deadline: i64::MAX,
+            settled: false,
+            bump,
+        };
+        let vault = pda(&[b"vault"]).0;
+        vec![
+            self.config(),
+            mint_account(self.mint, 6),
+            program_account(key, &resolution),
+            token_account(vault, self.mint, vault, 0),
+            disputer_token,
+            signer(self.user),
+            token_program(),
+        ]
+    }
+
+    fn refund_accounts(&self, user_token: AccountInfo<'static>) -> Vec<AccountInfo<'static>> {
+        let (vote_key, vote) = vote(self.market, self.user, 0);
+        vec![
+            self.config(),
+            mint_account(self.mint, 6),
+            self.market(),
+            program_account(vote_key, &vote),
+            self.market_vault(),
+            user_token,
+            signer(self.user),
+            token_program(),
+        ]
+    }
+}
+
+#[test]
+fn deposit_accepts_collateral_mint() {
+    let f = Fixture::new();
+    let user_token = token_account(Pubkey::new_unique(), f.mint, f.user, 1_000);
+    assert!(validate::<CompleteSet>(f.deposit_accounts(mint_account(f.mint, 6), user_token)).is_ok());
+}
+
+#[test]
+fn deposit_rejects_wrong_mint() {
+    let f = Fixture::new();
+    let user_token = token_account(Pubkey::new_unique(), Pubkey::new_unique(), f.user, 1_000);
+    let err = validate::<CompleteSet>(f.deposit_accounts(mint_account(f.mint, 6), user_token)).unwrap_err();
+    assert_eq!(err, error!(ErrorCode::ConstraintTokenMint));
+}
+
+#[test]
+fn deposit_rejects_substituted_mint_account() {
+    let f = Fixture::new();
+    // Token account and mint agree with each other, but not with the protocol's collateral
+    let fake_mint = Pubkey::new_unique();
+    let user_token = token_account(Pubkey::new_unique(), fake_mint, f.user, 1_000);
+    let err = validate::<CompleteSet>(f.deposit_accounts(mint_account(fake_mint, 6), user_token)).unwrap_err();
+    assert_eq!(err, error!(ErrorCode::ConstraintAddress));
+}
+
+#[test]
+fn deposit_rejects_token_account_of_another_owner() {
+    let f = Fixture::new();
+    let user_token = token_account(Pubkey::new_unique(), f.mint, Pubkey::new_unique(), 1_000);
+    let err = validate::<CompleteSet>(f.deposit_accounts(mint_account(f.mint, 6), user_token)).unwrap_err();
+    assert_eq!(err, error!(ErrorCode::ConstraintTokenOwner));
+}
+
+#[test]
+fn dispute_bond_rejects_wrong_mint() {
+    let f = Fixture::new();
+    assert!(validate::<DisputeResolution>(f.dispute_accounts(token_account(Pubkey::new_unique(), f.mint, f.user, 100))).is_ok());
+
+    let disputer_token = token_account(Pubkey::new_unique(), Pubkey::new_unique(), f.user, 100);
+    let err = validate::<DisputeResolution>(f.dispute_accounts(disputer_token)).unwrap_err();
+    assert_eq!(err, error!(ErrorCode::ConstraintTokenMint));
+}
+
+#[test]
+fn refund_rejects_wrong_mint() {
+    let f = Fixture::new();
+    assert!(validate::<ClaimRefund>(f.refund_accounts(token_account(Pubkey::new_unique(), f.mint, f.user, 0))).is_ok());
+
+    let user_token = token_account(Pubkey::new_unique(), Pubkey::new_unique(), f.user, 0);
+    let err = validate::<ClaimRefund>(f.refund_accounts(user_token)).unwrap_err();
+    assert_eq!(err, error!(ErrorCode::ConstraintTokenMint));
+}
+
+#[test]
+fn vote_rejects_wrong_mint() {
+    let f = Fixture::new();
+    let ledger = f.ledger(&f.open_market());
+    let vote = uninitialized(pda(&[b"vote", f.market.as_ref(), f.user.as_ref(), &0u64.to_le_bytes()]).0);
+    let place_vote = |mint: AccountInfo<'static>, user_token: AccountInfo<'static>| {
+        let accounts = [
+            ledger.market.clone(),
+            vote.clone(),
+            ledger.market_vault.clone(),
+            user_token,
+            ledger.config.clone(),
+            mint,
+            signer(f.user),
+            system_program(),
+            token_program(),
+        ];
+        run(polybet::instruction::PlaceVote { outcome_index: 0, amount: 1_000, nonce: 0 }, &accounts).unwrap_err()
+    };
+
+    let fake_mint = Pubkey::new_unique();
+    let err = place_vote(ledger.mint.clone(), token_account(Pubkey::new_unique(), fake_mint, f.user, 1_000));
+    assert_eq!(err, error!(ErrorCode::ConstraintTokenMint).into());
+    let err = place_vote(mint_account(fake_mint, 6), token_account(Pubkey::new_unique(), fake_mint, f.user, 1_000));
+    assert_eq!(err, error!(ErrorCode::ConstraintAddress).into());
+    assert_eq!(token_amount(&ledger.market_vault), 0);
+}
+
+/// Both ways of being paid in another token: a payout account of another mint, or a mint that isn't the collateral
+fn wrong_payout_mints(f: &Fixture, user: Pubkey) -> [(AccountInfo<'static>, AccountInfo<'static>, ErrorCode); 2] {
+    let fake_mint = Pubkey::new_unique();
+    [
+        (mint_account(f.mint, 6), token_account(Pubkey::new_unique(), fake_mint, user, 0), ErrorCode::ConstraintTokenMint),
+        (mint_account(fake_mint, 6), token_account(Pubkey::new_unique(), fake_mint, user, 0), ErrorCode::ConstraintAddress),
+    ]
+}
+
+#[test]
+fn winnings_reject_wrong_mint() {
+    let f = Fixture::new();
+    let ledger = f.ledger(&f.open_market());
+    let winner = ledger.wallet(1_000);
+    let vote = ledger.place_vote(&winner, 0, 0, 1_000).unwrap();
+    ledger.place_vote(&ledger.wallet(1_000), 0, 1, 1_000).unwrap();
+    ledger.resolve(0).unwrap();
+
+    for (mint, user_token, code) in wrong_payout_mints(&f, winner.key()) {
+        let accounts = [
+            ledger.config.clone(),
+            mint,
+            ledger.market.clone(),
+            vote.clone(),
+            ledger.market_vault.clone(),
+            user_token,
+            ledger.fee_vault.clone(),
+            winner.signer.clone(),
+            token_program(),
+        ];
+        let err = run(polybet::instruction::ClaimWinnings {}, &accounts).unwrap_err();
+        assert_eq!(err, error!(code).into());
+    }
+    assert_eq!(token_amount(&ledger.market_vault), 2_000);
+    ledger.claim_winnings(&winner, &vote).unwrap();
+}
+
+#[test]
+fn refund_payout_rejects_wrong_mint() {
+    let f = Fixture::new();
+    let ledger = f.ledger(&f.open_market());
+    let user = ledger.wallet(1_000);
+    let vote = ledger.place_vote(&user, 0, 0, 1_000).unwrap();
+    ledger.cancel(&ledger.creator.signer).unwrap();
+
+    for (mint, user_token, code) in wrong_payout_mints(&f, user.key()) {
+        let accounts = [
+            ledger.config.clone(),
+            mint,
+            ledger.market.clone(),
+            vote.clone(),
+            ledger.market_vault.clone(),
+            user_token,
+            user.signer.clone(),
+            token_program(),
+        ];
+        let err = run(polybet::instruction::ClaimRefund {}, &accounts).unwrap_err();
+        assert_eq!(err, error!(code).into());
+    }
+    assert_eq!(token_amount(&ledger.market_vault), 1_000);
+    ledger.claim_refund(&user, &vote).unwrap();
+    assert_eq!(token_amount(&user.token), 1_000);
+}
@@ -0,0 +1,415 @@
+//! Hand-built account fixtures for running `Accounts::try_accounts` outside a validator.
+#![allow(dead_code)]
+
+use std::collections::BTreeSet;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE};
+use anchor_lang::{Bumps, Discriminator};
+use anchor_spl::token_2022::spl_token_2022::{self, solana_program::program_pack::Pack, state::AccountState};
+use polybet::{Market, MarketKind, PricingMode, ProtocolConfig, Role, RoleAssignment, Vote};
+
+pub const TOKEN_2022_ID: Pubkey = spl_token_2022::ID;
+
+mod runtime;
+pub use runtime::*;
+
+/// Leaks the backing storage so the `AccountInfo` can live for the whole test
+fn leak_info(key: Pubkey, owner: Pubkey, data: Vec<u8>, is_signer: bool, is_writable: bool, executable: bool) -> AccountInfo<'static> {
+    AccountInfo::new(
+        leak_key(key, data.len()),
+        is_signer,
+        is_writable,
+        Box::leak(Box::new(1_000_000_000u64)),
+        leak_data(&data),
+        Box::leak(Box::new(owner)),
+        executable,
+        0,
+    )
+}
+
+/// `AccountInfo::realloc` (and so Anchor's `close`) reads the original data length from the 4 bytes before the key
+fn leak_key(key: Pubkey, original_data_len: usize) -> &'static Pubkey {
+    let buffer: &'static mut [u8] = Box::leak(vec![0; 4 + 32].into_boxed_slice());
+    buffer[..4].copy_from_slice(&(original_data_len as u32).to_le_bytes());
+    buffer[4..].copy_from_slice(key.as_ref());
+    unsafe { &*(buffer[4..].as_ptr() as *const Pubkey) }
+}
+
+/// Lays data out like the runtime's input buffer: an 8-byte length in front, room to grow behind
+fn leak_data(data: &[u8]) -> &'static mut [u8] {
+    let words = (8 + data.len() + MAX_PERMITTED_DATA_INCREASE).div_ceil(8);
+    let buffer: &'static mut [u8] = words_as_bytes(Box::leak(vec![0u64; words].into_boxed_slice()));
+    buffer[..8].copy_from_slice(&(data.len() as u64).to_le_bytes());
+    buffer[8..8 + data.len()].copy_from_slice(data);
+    &mut buffer[8..8 + data.len()]
+}
+
+/// The length header is written as a `u64`, so the buffer has to be word aligned
+fn words_as_bytes(words: &'static mut [u64]) -> &'static mut [u8] {
+    unsafe { std::slice::from_raw_parts_mut(words.as_mut_ptr() as *mut u8, words.len() * 8) }
+}
+
+pub fn program_account<T: AccountSerialize + Discriminator>(key: Pubkey, account: &T) -> AccountInfo<'static> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    leak_info(key, polybet::ID, data, false, true, false)
+}
+
+/// Same, with the account's full allocated `space` so it can grow (e.g. `Option`s being set)
+pub fn program_account_with_space<T: AccountSerialize + Discriminator>(key: Pubkey, account: &T, space: usize) -> AccountInfo<'static> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data.resize(space, 0);
+    leak_info(key, polybet::ID, data, false, true, false)
+}
+
+pub fn token_account(key: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) -> AccountInfo<'static> {
+    let mut data = vec![0; spl_token_2022::state::Account::LEN];
+    spl_token_2022::state::Account { mint, owner, amount, state: AccountState::Initialized, ..Default::default() }
+        .pack_into_slice(&mut data);
+    leak_info(key, TOKEN_2022_ID, data, false, true, false)
+}
+
+pub fn mint_account(key: Pubkey, decimals: u8) -> AccountInfo<'static> {
+    let mut data = vec![0; spl_token_2022::state::Mint::LEN];
+    spl_token_2022::state::Mint { decimals, is_initialized: true, supply: 1_000_000_000, ..Default::default() }
+        .pack_into_slice(&mut data);
+    leak_info(key, TOKEN_2022_ID, data, false, true, false)
+}
+
+pub fn signer(key: Pubkey) -> AccountInfo<'static> {
+    leak_info(key, System::id(), Vec::new(), true, true, false)
+}
+
+pub fn token_program() -> AccountInfo<'static> {
+    leak_info(TOKEN_2022_ID, Pubkey::default(), Vec::new(), false, false, true)
+}
+
+/// Placeholder for an omitted `Option<Account>`, which Anchor recognises by the program ID
+pub fn absent() -> AccountInfo<'static> {
+    leak_info(polybet::ID, Pubkey::default(), Vec::new(), false, false, true)
+}
+
+pub fn pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &polybet::ID)
+}
+
+/// Runs the account validation for `T` against `infos`, in struct order
+pub fn validate<T>(infos: Vec<AccountInfo<'static>>) -> Result<()>
+where
+    T: Accounts<'static, T::Bumps> + Bumps,
+    T::Bumps: Default,
+{
+    let infos: &'static [AccountInfo<'static>] = Box::leak(infos.into_boxed_slice());
+    let mut remaining = infos;
+    T::try_accounts(&polybet::ID, &mut remaining, &[], &mut T::Bumps::default(), &mut BTreeSet::new()).map(|_| ())
+}
+
+pub fn config(authority: Pubkey, mint: Pubkey, fee_recipient: Pubkey) -> ProtocolConfig {
+    ProtocolConfig {
+        authority,
+        pending_authority: None,
+        mint,
+        vault_bump: pda(&[b"vault"]).1,
+        resolution_bond: 0,
+        dispute_window: 86_400,
+        protocol_fee_bps: polybet::DEFAULT_PROTOCOL_FEE_BPS,
+        creator_fee_bps: 0,
+        fee_recipient,
+        bonds_outstanding: 0,
+        fee_vault_bump: pda(&[b"fee_vault"]).1,
+        total_burned: 0,
+        min_creator_balance: 0,
+        creation_bond: 0,
+        paused: false,
+        bump: pda(&[b"config"]).1,
+    }
+}
+
+pub fn market(market_key: Pubkey, authority: Pubkey) -> Market {
+    Market {
+        authority,
+        pending_authority: None,
+        oracle: authority,
+        question: "Will SOL close above $200?".to_string(),
+        end_timestamp: 0,
+        outcomes_count: 2,
+        outcome_names: vec!["Yes".to_string(), "No".to_string()],
+        total_pot: 0,
+        outcome_totals: [0; 8],
+        min_bet: 1,
+        max_bet: u64::MAX,
+        polymarket_id: String::new(),
+        metadata_url: String::new(),
+        kind: MarketKind::Standard,
+        pricing: PricingMode::FixedOdds,
+        shares: [0; 8],
+        outcome_mints_created: 0,
+        outcome_mint_bumps: [0; 8],
+        complete_sets: 0,
+        creator_fees: 0,
+        creator_fees_claimed: false,
+        creation_bond: 0,
+        subsidy: 0,
+        protocol_fee_bps: polybet::DEFAULT_PROTOCOL_FEE_BPS,
+        deposits: 0,
+        payouts_owed: [0; 8],
+        resolved: true,
+        winner_index: Some(0),
+        cancelled: false,
+        pending_resolution: false,
+        paused: false,
+        vault_bump: pda(&[b"market_vault", market_key.as_ref()]).1,
+        bump: 255,
+    }
+}
+
+pub fn vote(market: Pubkey, user: Pubkey, nonce: u64) -> (Pubkey, Vote) {
+    let (key, bump) = pda(&[b"vote", market.as_ref(), user.as_ref(), &nonce.to_le_bytes()]);
+    let vote = Vote {
+        user,
+        market,
+        outcome_index: 0,
+        nonce,
+        amount: 100,
+        locked_payout: 180,
+        locked_creator_fee: 0,
+        locked_dev_fee: 18,
+        claimed: false,
+        bump,
+    };
+    (key, vote)
+}
+
+pub fn role(role: Role, member: Pubkey) -> AccountInfo<'static> {
+    let (key, bump) = pda(&[b"role", &[role as u8], member.as_ref()]);
+    program_account(key, &RoleAssignment { role, member, bump })
+}
+
+/// Keys shared by one protocol, one market and one bettor
+pub struct Fixture {
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+    pub user: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub market: Pubkey,
+}
+
+impl Fixture {
+    pub fn new() -> Self {
+        Fixture {
+            mint: Pubkey::new_unique(),
+            admin: Pubkey::new_unique(),
+            user: Pubkey::new_unique(),
+            fee_recipient: Pubkey::new_unique(),
+            market: Pubkey::new_unique(),
+        }
+    }
+
+    pub fn config(&self) -> AccountInfo<'static> {
+        program_account(pda(&[b"config"]).0, &config(self.admin, self.mint, self.fee_recipient))
+    }
+
+    pub fn market(&self) -> AccountInfo<'static> {
+        program_account(self.market, &market(self.market, self.admin))
+    }
+
+    pub fn market_vault(&self) -> AccountInfo<'static> {
+        let key = pda(&[b"market_vault", self.market.as_ref()]).0;
+        token_account(key, self.mint, key, 1_000)
+    }
+}
+
+/// A wallet that signs and holds collateral
+#[derive(Debug)]
+pub struct Wallet {
+    pub signer: AccountInfo<'static>,
+    pub token: AccountInfo<'static>,
+}
+
+impl Wallet {
+    pub fn key(&self) -> Pubkey {
+        self.signer.key()
+    }
+}
+
+/// Accounts that keep their state across instructions, so a market can be run end to end
+#[derive(Debug)]
+pub struct Ledger {
+    pub config: AccountInfo<'static>,
+    pub mint: AccountInfo<'static>,
+    pub market: AccountInfo<'static>,
+    pub market_vault: AccountInfo<'static>,
+    pub treasury_vault: AccountInfo<'static>,
+    pub fee_vault: AccountInfo<'static>,
+    pub creator: Wallet,
+}
+
+impl Fixture {
+    /// Open until `end_timestamp` 1,000 with 1,000 / 1,000 of virtual liquidity
+    pub fn open_market(&self) -> Market {
+        let mut market = market(self.market, self.admin);
+        market.resolved = false;
+        market.winner_index = None;
+        market.end_timestamp = 1_000;
+        market.total_pot = 2_000;
+        market.outcome_totals[..2].copy_from_slice(&[1_000, 1_000]);
+        market
+    }
+
+    pub fn ledger(&self, market: &Market) -> Ledger {
+        let market_vault = pda(&[b"market_vault", self.market.as_ref()]).0;
+        let treasury = pda(&[b"vault"]).0;
+        Ledger {
+            config: self.config(),
+            mint: mint_account(self.mint, 6),
+            market: program_account_with_space(self.market, market, Market::SPACE),
+            market_vault: token_account(market_vault, self.mint, market_vault, 0),
+            treasury_vault: token_account(treasury, self.mint, treasury, 0),
+            fee_vault: token_account(self.fee_recipient, self.mint, self.admin, 0),
+            creator: Wallet { signer: signer(self.admin), token: token_account(Pubkey::new_unique(), self.mint, self.admin, 0) },
+        }
+    }
+}
+
+impl Ledger {
+    pub fn market(&self) -> Market {
+        read(&self.market)
+    }
+
+    pub fn set_market(&self, market: &Market) {
+        let mut data = Vec::new();
+        market.try_serialize(&mut data).unwrap();
+        self.market.data.borrow_mut()[..data.len()].copy_from_slice(&data);
+    }
+
+    pub fn set_config(&self, config: &ProtocolConfig) {
+        let mut data = Vec::new();
+        config.try_serialize(&mut data).unwrap();
+        self.config.data.borrow_mut().copy_from_slice(&data);
+    }
+
+    pub fn wallet(&self, balance: u64) -> Wallet {
+        let key = Pubkey::new_unique();
+        Wallet { signer: signer(key), token: token_account(Pubkey::new_unique(), self.mint.key(), key, balance) }
+    }
+
+    /// Opens a second even-odds YES/NO market from `creator`, sharing this ledger's protocol accounts
+    pub fn initialize_market(&self, creator: &Wallet, question: &str) -> std::result::Result<Ledger, ProgramError> {
+        let market = uninitialized(pda(&[b"market", creator.key().as_ref(), question.as_bytes()]).0);
+        let market_vault = uninitialized(pda(&[b"market_vault", market.key.as_ref()]).0);
+        let accounts = [
+            market.clone(),
+            market_vault.clone(),
+            self.config.clone(),
+            self.mint.clone(),
+            creator.token.clone(),
+            creator.signer.clone(),
+            system_program(),
+            token_program(),
+        ];
+        let ix = polybet::instruction::InitializeMarket {
+            question: question.to_string(),
+            end_timestamp: 1_000,
+            outcomes_count: 2,
+            virtual_liquidity: 2_000,
+            weights: [1, 1, 0, 0, 0, 0, 0, 0],
+            oracle: creator.key(),
+            outcome_names: vec!["Yes".to_string(), "No".to_string()],
+            min_bet: 1,
+            max_bet: u64::MAX,
+            polymarket_id: String::new(),
+            metadata_url: String::new(),
+            kind: MarketKind::Standard,
+            pricing: PricingMode::FixedOdds,
+        };
+        run(ix, &accounts)?;
+        Ok(Ledger {
+            config: self.config.clone(),
+            mint: self.mint.clone(),
+            market,
+            market_vault,
+            treasury_vault: self.treasury_vault.clone(),
+            fee_vault: self.fee_vault.clone(),
+            creator: Wallet { signer: creator.signer.clone(), token: creator.token.clone() },
+        })
+    }
+
+    /// Returns the new vote account
+    pub fn place_vote(&self, user: &Wallet, nonce: u64, outcome_index: u8, amount: u64) -> std::result::Result<AccountInfo<'static>, ProgramError> {
+        let vote = uninitialized(pda(&[b"vote", self.market.key.as_ref(), user.key().as_ref(), &nonce.to_le_bytes()]).0);
+        let accounts = [
+            self.market.clone(),
+            vote.clone(),
+            self.market_vault.clone(),
+            user.token.clone(),
+            self.config.clone(),
+            self.mint.clone(),
+            user.signer.clone(),
+            system_program(),
+            token_program(),
+        ];
+        run(polybet::instruction::PlaceVote { outcome_index, amount, nonce }, &accounts).map(|_| vote)
+    }
+
+    pub fn claim_winnings(&self, user: &Wallet, vote: &AccountInfo<'static>) -> ProgramResult {
+        let accounts = [
+            self.config.clone(),
+            self.mint.clone(),
+            self.market.clone(),
+            vote.clone(),
+            self.market_vault.clone(),
+            user.token.clone(),
+            self.fee_vault.clone(),
+            user.signer.clone(),
+            token_program(),
+        ];
+        run(polybet::instruction::ClaimWinnings {}, &accounts)
+    }
+
+    pub fn claim_refund(&self, user: &Wallet, vote: &AccountInfo<'static>) -> ProgramResult {
+        let accounts = [
+            self.config.clone(),
+            self.mint.clone(),
+            self.market.clone(),
+            vote.clone(),
+            self.market_vault.clone(),
+            user.token.clone(),
+            user.signer.clone(),
+            token_program(),
+        ];
+        run(polybet::instruction::ClaimRefund {}, &accounts)
+    }
+
+    pub fn cash_out(&self, user: &Wallet, vote: &AccountInfo<'static>) -> ProgramResult {
+        let accounts = [
+            self.config.clone(),
+            self.mint.clone(),
+            self.market.clone(),
+            vote.clone(),
+            self.market_vault.clone(),
+            user.token.clone(),
+            user.signer.clone(),
+            token_program(),
+        ];
+        run(polybet::instruction::CashOut {}, &accounts)
+    }
+
+    pub fn resolve(&self, winner_index: u8) -> ProgramResult {
+        run(polybet::instruction::ResolveMarket { winner_index }, &[self.market.clone(), self.creator.signer.clone(), absent()])
+    }
+
+    pub fn cancel(&self, authority: &AccountInfo<'static>) -> ProgramResult {
+        let accounts = [
+            self.config.clone(),
+            self.mint.clone(),
+            self.market.clone(),
+            self.market_vault.clone(),
+            self.treasury_vault.clone(),
+            authority.clone(),
+            token_program(),
+        ];
+        run(polybet::instruction::CancelMarket {}, &accounts)
+    }
+}
@@ -0,0 +1,148 @@
+//! Just enough of the runtime to run whole instructions on the host: the clock and rent sysvars,
+//! and CPIs into the System and Token-2022 programs applied to the caller's own `AccountInfo`s.
+
+use std::cell::{Cell, RefCell};
+use std::sync::Once;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use anchor_lang::solana_program::system_program;
+use anchor_lang::{AccountDeserialize, InstructionData};
+use anchor_spl::token_2022::spl_token_2022::{self, solana_program::program_pack::Pack};
+
+use super::{leak_data, leak_info, TOKEN_2022_ID};
+
+thread_local! {
+    static NOW: Cell<i64> = const { Cell::new(0) };
+    static RETURN_DATA: RefCell<Option<(Pubkey, Vec<u8>)>> = const { RefCell::new(None) };
+}
+
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_log_data(&self, _fields: &[&[u8]]) {}
+
+    fn sol_invoke_signed(&self, instruction: &Instruction, account_infos: &[AccountInfo], signers_seeds: &[&[&[u8]]]) -> ProgramResult {
+        invoke(instruction, account_infos, signers_seeds)
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock { unix_timestamp: NOW.get(), ..Clock::default() };
+        unsafe { *(var_addr as *mut Clock) = clock };
+        0
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        0
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        RETURN_DATA.with_borrow(Clone::clone)
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        RETURN_DATA.set(Some((TOKEN_2022_ID, data.to_vec())));
+    }
+}
+
+/// Sets the `Clock::unix_timestamp` seen by instructions run on this thread
+pub fn set_now(unix_timestamp: i64) {
+    NOW.set(unix_timestamp);
+}
+
+/// Runs `ix` through the program entrypoint against `accounts`, in struct order
+pub fn run<I: InstructionData>(ix: I, accounts: &[AccountInfo<'static>]) -> ProgramResult {
+    static STUBS: Once = Once::new();
+    STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(Stubs));
+    });
+    // Clones share their lamports and data with `accounts`, so the caller sees every change
+    let accounts: &'static [AccountInfo<'static>] = Box::leak(accounts.to_vec().into_boxed_slice());
+    polybet::entry(&polybet::ID, accounts, &ix.data())
+}
+
+/// Signers are checked like the runtime does: real signatures carry over, PDAs need their seeds
+fn invoke(instruction: &Instruction, account_infos: &[AccountInfo], signers_seeds: &[&[&[u8]]]) -> ProgramResult {
+    let pdas: Vec<Pubkey> = signers_seeds
+        .iter()
+        .map(|seeds| Pubkey::create_program_address(seeds, &polybet::ID).unwrap())
+        .collect();
+    let accounts = instruction
+        .accounts
+        .iter()
+        .map(|meta| {
+            let mut info = account_infos.iter().find(|info| *info.key == meta.pubkey).expect("account missing from CPI").clone();
+            if meta.is_signer && !info.is_signer {
+                assert!(pdas.contains(&meta.pubkey), "{} signs without seeds", meta.pubkey);
+                info.is_signer = true;
+            }
+            info
+        })
+        .collect::<Vec<_>>();
+
+    match instruction.program_id {
+        TOKEN_2022_ID => spl_token_2022::processor::Processor::process(&TOKEN_2022_ID, &accounts, &instruction.data),
+        system_program::ID => system(&accounts, &instruction.data),
+        program_id => panic!("unexpected CPI into {program_id}"),
+    }
+}
+
+/// The System program instructions Anchor's `init` uses
+fn system(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let u64_at = |at: usize| u64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+    let pubkey_at = |at: usize| Pubkey::try_from(&data[at..at + 32]).unwrap();
+    match u32::from_le_bytes(data[..4].try_into().unwrap()) {
+        // CreateAccount { lamports, space, owner }
+        0 => {
+            transfer(&accounts[0], &accounts[1], u64_at(4));
+            allocate(&accounts[1], u64_at(12));
+            accounts[1].assign(&pubkey_at(20));
+        }
+        // Assign { owner }
+        1 => accounts[0].assign(&pubkey_at(4)),
+        // Transfer { lamports }
+        2 => transfer(&accounts[0], &accounts[1], u64_at(4)),
+        // Allocate { space }
+        8 => allocate(&accounts[0], u64_at(4)),
+        tag => panic!("unexpected system instruction {tag}"),
+    }
+    Ok(())
+}
+
+fn transfer(from: &AccountInfo, to: &AccountInfo, lamports: u64) {
+    **from.lamports.borrow_mut() -= lamports;
+    **to.lamports.borrow_mut() += lamports;
+}
+
+fn allocate(account: &AccountInfo, space: u64) {
+    *account.data.borrow_mut() = leak_data(&vec![0; space as usize]);
+}
+
+/// An address nothing has been created at yet, for `init` accounts
+pub fn uninitialized(key: Pubkey) -> AccountInfo<'static> {
+    let info = leak_info(key, system_program::ID, Vec::new(), false, true, false);
+    **info.lamports.borrow_mut() = 0;
+    info
+}
+
+pub fn system_program() -> AccountInfo<'static> {
+    leak_info(system_program::ID, Pubkey::default(), Vec::new(), false, false, true)
+}
+
+/// Reads a program account back after an instruction ran
+pub fn read<T: AccountDeserialize>(info: &AccountInfo) -> T {
+    T::try_deserialize(&mut &info.data.borrow()[..]).unwrap()
+}
+
+pub fn token_amount(info: &AccountInfo) -> u64 {
+    spl_token_2022::state::Account::unpack_from_slice(&info.data.borrow()[..spl_token_2022::state::Account::LEN]).unwrap().amount
+}
+
+pub fn mint_supply(info: &AccountInfo) -> u64 {
+    spl_token_2022::state::Mint::unpack_from_slice(&info.data.borrow()[..spl_token_2022::state::Mint::LEN]).unwrap().supply
+}
@@ -0,0 +1,24 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::*;
+use polybet::{PolybetError, ProtocolConfig};
+
+#[test]
+fn creator_below_minimum_balance_is_rejected() {
+    let f = Fixture::new();
+    let ledger = f.ledger(&f.open_market());
+    let mut config: ProtocolConfig = read(&ledger.config);
+    config.min_creator_balance = 10_000;
+    ledger.set_config(&config);
+
+    let err = ledger.initialize_market(&ledger.wallet(9_999), "Below the gate?").unwrap_err();
+    assert_eq!(err, error!(PolybetError::CreatorBalanceTooLow).into());
+
+    // Holding the minimum is enough, nothing is spent without a bond
+    let creator = ledger.wallet(10_000);
+    let created = ledger.initialize_market(&creator, "At the gate?").unwrap();
+    assert_eq!(created.market().authority, creator.key());
+    assert_eq!(created.market().total_pot, 2_000);
+    assert_eq!(token_amount(&creator.token), 10_000);
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use common::*;
+use polybet::{PolybetError, ProtocolConfig};
+
+fn reclaim_creation_bond(ledger: &Ledger) -> ProgramResult {
+    let accounts = [
+        ledger.config.clone(),
+        ledger.mint.clone(),
+        ledger.market.clone(),
+        ledger.market_vault.clone(),
+        ledger.creator.token.clone(),
+        ledger.creator.signer.clone(),
+        token_program(),
+    ];
+    run(polybet::instruction::ReclaimCreationBond {}, &accounts)
+}
+
+/// A market opened by a creator holding 1,000 under a 100 creation bond
+fn bonded(f: &Fixture) -> Ledger {
+    let protocol = f.ledger(&f.open_market());
+    let mut config: ProtocolConfig = read(&protocol.config);
+    config.creation_bond = 100;
+    protocol.set_config(&config);
+
+    let ledger = protocol.initialize_market(&protocol.wallet(1_000), "Bonded?").unwrap();
+    assert_eq!(token_amount(&ledger.creator.token), 900);
+    assert_eq!(token_amount(&ledger.market_vault), 100);
+    assert_eq!(ledger.market().creation_bond, 100);
+    ledger
+}
+
+#[test]
+fn bond_is_slashed_when_the_creator_cancels() {
+    let f = Fixture::new();
+    let ledger = bonded(&f);
+    let err = reclaim_creation_bond(&ledger).unwrap_err();
+    assert_eq!(err, error!(PolybetError::MarketActive).into());
+
+    ledger.cancel(&ledger.creator.signer).unwrap();
+    assert_eq!(token_amount(&ledger.treasury_vault), 100);
+    assert_eq!(token_amount(&ledger.market_vault), 0);
+    assert_eq!(ledger.market().creation_bond, 0);
+
+    let err = reclaim_creation_bond(&ledger).unwrap_err();
+    assert_eq!(err, error!(PolybetError::MarketActive).into());
+    assert_eq!(token_amount(&ledger.creator.token), 900);
+}
+
+#[test]
+fn bond_is_returned_once_after_resolution() {
+    let f = Fixture::new();
+    let ledger = bonded(&f);
+    ledger.resolve(0).unwrap();
+
+    reclaim_creation_bond(&ledger).unwrap();
+    assert_eq!(token_amount(&ledger.creator.token), 1_000);
+    assert_eq!(token_amount(&ledger.market_vault), 0);
+
+    let err = reclaim_creation_bond(&ledger).unwrap_err();
+    assert_eq!(err, error!(PolybetError::AlreadyClaimed).into());
+    assert_eq!(token_amount(&ledger.treasury_vault), 0);
+}
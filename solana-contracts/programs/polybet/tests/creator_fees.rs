@@ -0,0 +1,46 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use common::*;
+use polybet::{PolybetError, ProtocolConfig, Vote};
+
+fn claim_creator_fees(ledger: &Ledger) -> ProgramResult {
+    let accounts = [
+        ledger.config.clone(),
+        ledger.mint.clone(),
+        ledger.market.clone(),
+        ledger.market_vault.clone(),
+        ledger.creator.token.clone(),
+        ledger.creator.signer.clone(),
+        token_program(),
+    ];
+    run(polybet::instruction::ClaimCreatorFees {}, &accounts)
+}
+
+#[test]
+fn creator_fees_are_claimed_once_after_resolution() {
+    let f = Fixture::new();
+    let ledger = f.ledger(&f.open_market());
+    let mut config: ProtocolConfig = read(&ledger.config);
+    config.creator_fee_bps = 500;
+    ledger.set_config(&config);
+
+    // 5% of the 1,000 bet is set aside before the stake is priced
+    let vote = ledger.place_vote(&ledger.wallet(1_000), 0, 0, 1_000).unwrap();
+    assert_eq!(ledger.market().creator_fees, 50);
+    assert_eq!(read::<Vote>(&vote).locked_creator_fee, 50);
+
+    let err = claim_creator_fees(&ledger).unwrap_err();
+    assert_eq!(err, error!(PolybetError::MarketActive).into());
+
+    ledger.resolve(1).unwrap();
+    claim_creator_fees(&ledger).unwrap();
+    assert_eq!(token_amount(&ledger.creator.token), 50);
+    assert!(ledger.market().creator_fees_claimed);
+
+    let err = claim_creator_fees(&ledger).unwrap_err();
+    assert_eq!(err, error!(PolybetError::AlreadyClaimed).into());
+    assert_eq!(token_amount(&ledger.creator.token), 50);
+    assert_eq!(token_amount(&ledger.market_vault), 950);
+}
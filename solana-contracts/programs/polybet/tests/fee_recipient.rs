@@ -0,0 +1,80 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::*;
+use polybet::{ClaimWinnings, PolybetError, SetFeeRecipient};
+
+impl Fixture {
+    fn claim_accounts(&self, dev_token: AccountInfo<'static>) -> Vec<AccountInfo<'static>> {
+        let (vote_key, vote) = vote(self.market, self.user, 0);
+        vec![
+            self.config(),
+            mint_account(self.mint, 6),
+            self.market(),
+            program_account(vote_key, &vote),
+            self.market_vault(),
+            token_account(Pubkey::new_unique(), self.mint, self.user, 0),
+            dev_token,
+            signer(self.user),
+            token_program(),
+        ]
+    }
+
+    fn set_recipient_accounts(&self, fee_recipient: AccountInfo<'static>, signer_key: Pubkey) -> Vec<AccountInfo<'static>> {
+        vec![
+            self.config(),
+            fee_recipient,
+            signer(signer_key),
+            absent(),
+        ]
+    }
+}
+
+#[test]
+fn claim_accepts_configured_fee_account() {
+    let f = Fixture::new();
+    let dev_token = token_account(f.fee_recipient, f.mint, f.admin, 0);
+    assert!(validate::<ClaimWinnings>(f.claim_accounts(dev_token)).is_ok());
+}
+
+#[test]
+fn claim_rejects_spoofed_fee_account() {
+    let f = Fixture::new();
+    // Claimer routes the protocol fee to a token account they own
+    let spoofed = token_account(Pubkey::new_unique(), f.mint, f.user, 0);
+    let err = validate::<ClaimWinnings>(f.claim_accounts(spoofed)).unwrap_err();
+    assert_eq!(err, error!(PolybetError::InvalidFeeRecipient));
+}
+
+#[test]
+fn claim_rejects_fee_account_with_recipient_owner_but_other_address() {
+    let f = Fixture::new();
+    // Right owner and mint is not enough, the exact configured account is required
+    let lookalike = token_account(Pubkey::new_unique(), f.mint, f.admin, 0);
+    let err = validate::<ClaimWinnings>(f.claim_accounts(lookalike)).unwrap_err();
+    assert_eq!(err, error!(PolybetError::InvalidFeeRecipient));
+}
+
+#[test]
+fn authority_can_rotate_fee_recipient() {
+    let f = Fixture::new();
+    let next = token_account(Pubkey::new_unique(), f.mint, Pubkey::new_unique(), 0);
+    assert!(validate::<SetFeeRecipient>(f.set_recipient_accounts(next, f.admin)).is_ok());
+}
+
+#[test]
+fn rotation_rejects_wrong_mint() {
+    let f = Fixture::new();
+    let next = token_account(Pubkey::new_unique(), Pubkey::new_unique(), f.admin, 0);
+    let err = validate::<SetFeeRecipient>(f.set_recipient_accounts(next, f.admin)).unwrap_err();
+    assert_eq!(err, error!(PolybetError::InvalidFeeRecipient));
+}
+
+#[test]
+fn rotation_rejects_non_authority() {
+    let f = Fixture::new();
+    let attacker = Pubkey::new_unique();
+    let next = token_account(Pubkey::new_unique(), f.mint, attacker, 0);
+    let err = validate::<SetFeeRecipient>(f.set_recipient_accounts(next, attacker)).unwrap_err();
+    assert_eq!(err, error!(PolybetError::Unauthorized));
+}
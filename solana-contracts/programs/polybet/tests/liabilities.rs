@@ -0,0 +1,121 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::*;
+use polybet::{Market, PricingMode};
+
+/// Two votes of 100 (2 creator fee each): one on YES locked at 180 + 18, one on NO at 150 + 15
+fn open_market(pricing: PricingMode) -> Market {
+    let key = Pubkey::new_unique();
+    let mut market = market(key, Pubkey::new_unique());
+    market.resolved = false;
+    market.winner_index = None;
+    market.pricing = pricing;
+    market.deposits = 200;
+    market.creator_fees = 4;
+    market.total_pot = 196;
+    market.outcome_totals[..2].copy_from_slice(&[98, 98]);
+    market.payouts_owed[..2].copy_from_slice(&match pricing {
+        PricingMode::Parimutuel => [98, 98],
+        _ => [198, 165],
+    });
+    market
+}
+
+#[test]
+fn open_market_reserves_worst_outcome() {
+    let market = open_market(PricingMode::FixedOdds);
+    assert_eq!(market.liabilities(), Some(198 + 4));
+}
+
+#[test]
+fn open_market_reserves_refunds_when_larger() {
+    let mut market = open_market(PricingMode::FixedOdds);
+    market.payouts_owed[..2].copy_from_slice(&[50, 60]);
+    assert_eq!(market.liabilities(), Some(200));
+}
+
+#[test]
+fn resolved_market_reserves_only_winners() {
+    let mut market = open_market(PricingMode::FixedOdds);
+    market.resolved = true;
+    market.winner_index = Some(1);
+    assert_eq!(market.liabilities(), Some(165 + 4));
+
+    market.creator_fees_claimed = true;
+    market.payouts_owed[1] = 0;
+    assert_eq!(market.liabilities(), Some(0));
+}
+
+#[test]
+fn cancelled_market_reserves_every_deposit() {
+    let mut market = open_market(PricingMode::FixedOdds);
+    market.cancelled = true;
+    assert_eq!(market.liabilities(), Some(200));
+}
+
+#[test]
+fn parimutuel_reserves_whole_pool() {
+    let mut market = open_market(PricingMode::Parimutuel);
+    assert_eq!(market.liabilities(), Some(200));
+
+    market.resolved = true;
+    market.winner_index = Some(0);
+    assert_eq!(market.liabilities(), Some(196 + 4));
+
+    // Nobody backed the winner, so only the creator is owed
+    market.payouts_owed[0] = 0;
+    market.outcome_totals[0] = 0;
+    assert_eq!(market.liabilities(), Some(4));
+}
+
+#[test]
+fn complete_sets_stay_reserved() {
+    let mut market = open_market(PricingMode::FixedOdds);
+    market.complete_sets = 1_000;
+    assert_eq!(market.liabilities(), Some(198 + 4 + 1_000));
+
+    market.cancelled = true;
+    assert_eq!(market.liabilities(), Some(200 + 1_000));
+}
+
+#[test]
+fn creation_bond_stays_reserved_until_paid_out() {
+    let mut market = open_market(PricingMode::FixedOdds);
+    market.creation_bond = 500;
+    assert_eq!(market.liabilities(), Some(198 + 4 + 500));
+
+    market.resolved = true;
+    market.winner_index = Some(0);
+    market.payouts_owed[0] = 0;
+    market.creator_fees_claimed = true;
+    assert_eq!(market.liabilities(), Some(500));
+}
+
+#[test]
+fn lmsr_subsidy_stays_reserved() {
+    let mut market = open_market(PricingMode::Lmsr { b: 1_000 });
+    market.subsidy = 694;
+    assert_eq!(market.liabilities(), Some(198 + 4 + 694));
+
+    market.cancelled = true;
+    assert_eq!(market.liabilities(), Some(200 + 694));
+}
+
+#[test]
+fn subsidy_refund_covers_winners_first() {
+    let mut market = open_market(PricingMode::Lmsr { b: 1_000 });
+    market.subsidy = 694;
+    market.resolved = true;
+    market.winner_index = Some(0);
+    market.payouts_owed[0] = 500;
+    // Vault holds 200 of deposits plus the subsidy: winners take 300 of it beyond their deposits
+    assert_eq!(market.refundable_subsidy(200 + 694), Some(694 - 300 - 4));
+
+    market.payouts_owed[0] = 100;
+    assert_eq!(market.refundable_subsidy(200 + 694), Some(694));
+
+    // Winners can't be paid in full, so nothing goes back
+    market.payouts_owed[0] = 2_000;
+    assert_eq!(market.refundable_subsidy(200 + 694), Some(0));
+}
@@ -0,0 +1,97 @@
+use polybet::lmsr::{cost, exp_wad, ln_wad, max_loss, proceeds_for_shares, shares_for_cost, WAD};
+
+const B: u64 = 1_000_000_000;
+
+fn assert_close(actual: i128, expected: f64, tolerance: f64) {
+    let actual = actual as f64 / WAD as f64;
+    assert!((actual - expected).abs() <= tolerance, "{actual} != {expected}");
+}
+
+#[test]
+fn exp_matches_float() {
+    for x in [-41.5, -10.0, -1.0, -0.25, 0.0, 0.5, 1.0, 5.0, 20.0] {
+        let actual = exp_wad((x * WAD as f64) as i128).unwrap();
+        assert_close(actual, f64::exp(x), f64::exp(x) * 1e-12 + 1e-18);
+    }
+}
+
+#[test]
+fn exp_bounds() {
+    assert_eq!(exp_wad(-43 * WAD), Some(0));
+    assert_eq!(exp_wad(41 * WAD), None);
+}
+
+#[test]
+fn ln_matches_float() {
+    for x in [1e-9, 0.3, 1.0, 2.0, std::f64::consts::E, 8.0, 1e12] {
+        let actual = ln_wad((x * WAD as f64) as i128).unwrap();
+        assert_close(actual, f64::ln(x), 1e-9);
+    }
+    assert_eq!(ln_wad(0), None);
+}
+
+#[test]
+fn initial_cost_is_b_ln_n() {
+    let shares = [0u64; 4];
+    assert_close(cost(&shares, B).unwrap() / B as i128, f64::ln(4.0), 1e-9);
+}
+
+#[test]
+fn buy_then_sell_round_trips_without_profit() {
+    let mut shares = [0u64; 2];
+    let amount = 250_000_000;
+    let bought = shares_for_cost(&shares, B, 0, amount).unwrap();
+    // Uniform prices start at 0.5, so the first purchase gets just under 2 shares per token
+    assert!(bought > amount && bought < 2 * amount);
+
+    shares[0] = bought;
+    let proceeds = proceeds_for_shares(&shares, B, 0, bought).unwrap();
+    assert!(proceeds <= amount);
+    assert!(amount - proceeds <= 2);
+}
+
+#[test]
+fn prices_move_against_repeat_buyers() {
+    let mut shares = [0u64; 2];
+    let first = shares_for_cost(&shares, B, 1, 100_000_000).unwrap();
+    shares[1] = first;
+    let second = shares_for_cost(&shares, B, 1, 100_000_000).unwrap();
+    assert!(second < first);
+}
+
+#[test]
+fn loss_is_bounded_by_subsidy() {
+    let bound = max_loss(B, 3).unwrap();
+    assert_eq!(bound, (B as f64 * f64::ln(3.0)).ceil() as u64);
+
+    // Pour far more than `b` into one outcome: payout never exceeds stake plus the subsidy
+    let mut shares = [0u64; 3];
+    let mut staked = 0u64;
+    for _ in 0..20 {
+        let amount = 2 * B;
+        shares[2] += shares_for_cost(&shares, B, 2, amount).unwrap();
+        staked += amount;
+    }
+    assert!(shares[2] <= staked + bound);
+}
+
+#[test]
+fn rejects_degenerate_inputs() {
+    assert_eq!(shares_for_cost(&[0, 0], 0, 0, 1), None);
+    assert_eq!(shares_for_cost(&[0, 0], B, 2, 1), None);
+    assert_eq!(proceeds_for_shares(&[5, 0], B, 0, 6), None);
+}
+
+#[test]
+fn single_bet_far_above_b_is_priced() {
+    // exp(amount / b) alone would overflow past ~40 * b; the bet still prices along the curve
+    for amount in [3 * B, 4 * B, 41 * B, 1_000 * B] {
+        let mut shares = [0u64; 8];
+        let bought = shares_for_cost(&shares, B, 0, amount).unwrap();
+        assert!(bought > amount && bought <= amount + max_loss(B, 8).unwrap());
+
+        shares[0] = bought;
+        let proceeds = proceeds_for_shares(&shares, B, 0, bought).unwrap();
+        assert!(proceeds <= amount && amount - proceeds <= 2, "{amount}: {proceeds}");
+    }
+}
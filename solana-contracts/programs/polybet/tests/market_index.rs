@@ -0,0 +1,48 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use common::*;
+use polybet::{MarketIndex, PolybetError, ReindexMarket, Role};
+
+const POLYMARKET_ID: &str = "0x5f3c";
+
+fn reindex_accounts(admin: Pubkey, polymarket_id: &str, signer_key: Pubkey, role_account: AccountInfo<'static>) -> Vec<AccountInfo<'static>> {
+    let market_key = Pubkey::new_unique();
+    let mut mirror = market(market_key, Pubkey::new_unique());
+    mirror.polymarket_id = polymarket_id.to_string();
+    let (index_key, bump) = pda(&[b"polymarket", hash(POLYMARKET_ID.as_bytes()).as_ref()]);
+    // Currently squatted by some other market
+    let index = MarketIndex { market: Pubkey::new_unique(), bump };
+    vec![
+        program_account(pda(&[b"config"]).0, &config(admin, Pubkey::new_unique(), Pubkey::new_unique())),
+        program_account(market_key, &mirror),
+        program_account(index_key, &index),
+        signer(signer_key),
+        role_account,
+    ]
+}
+
+#[test]
+fn protocol_can_repoint_entry() {
+    let admin = Pubkey::new_unique();
+    assert!(validate::<ReindexMarket>(reindex_accounts(admin, POLYMARKET_ID, admin, absent())).is_ok());
+
+    let ops = Pubkey::new_unique();
+    assert!(validate::<ReindexMarket>(reindex_accounts(admin, POLYMARKET_ID, ops, role(Role::Admin, ops))).is_ok());
+}
+
+#[test]
+fn market_creator_cannot_repoint_entry() {
+    let creator = Pubkey::new_unique();
+    let err = validate::<ReindexMarket>(reindex_accounts(Pubkey::new_unique(), POLYMARKET_ID, creator, absent())).unwrap_err();
+    assert_eq!(err, error!(PolybetError::Unauthorized));
+}
+
+#[test]
+fn entry_only_takes_market_with_same_id() {
+    let admin = Pubkey::new_unique();
+    let err = validate::<ReindexMarket>(reindex_accounts(admin, "0x9999", admin, absent())).unwrap_err();
+    assert_eq!(err, error!(ErrorCode::ConstraintSeeds));
+}
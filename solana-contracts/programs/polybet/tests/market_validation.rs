@@ -0,0 +1,216 @@
+use anchor_lang::prelude::*;
+use polybet::validation::{check_market, check_outcome, check_winner, MarketParams};
+use polybet::{Comparison, MarketKind, PolybetError, PricingMode};
+
+const NOW: i64 = 1_750_000_000;
+
+struct Args {
+    question: String,
+    end_timestamp: i64,
+    outcomes_count: u8,
+    virtual_liquidity: u64,
+    weights: [u32; 8],
+    outcome_names: Vec<String>,
+    min_bet: u64,
+    max_bet: u64,
+    polymarket_id: String,
+    metadata_url: String,
+    kind: MarketKind,
+    pricing: PricingMode,
+}
+
+impl Args {
+    /// A valid YES/NO market with 1,000 of virtual liquidity split evenly
+    fn binary() -> Self {
+        Args {
+            question: "Will SOL close above $200?".to_string(),
+            end_timestamp: NOW + 86_400,
+            outcomes_count: 2,
+            virtual_liquidity: 1_000,
+            weights: [1, 1, 0, 0, 0, 0, 0, 0],
+            outcome_names: vec!["Yes".to_string(), "No".to_string()],
+            min_bet: 1,
+            max_bet: 1_000_000,
+            polymarket_id: String::new(),
+            metadata_url: String::new(),
+            kind: MarketKind::Standard,
+            pricing: PricingMode::FixedOdds,
+        }
+    }
+
+    fn with_outcomes(count: u8) -> Self {
+        let mut args = Args::binary();
+        args.outcomes_count = count;
+        args.outcome_names = (0..count).map(|i| format!("Outcome {i}")).collect();
+        args
+    }
+
+    fn check(&self) -> Result<()> {
+        check_market(&MarketParams {
+            question: &self.question,
+            end_timestamp: self.end_timestamp,
+            outcomes_count: self.outcomes_count,
+            virtual_liquidity: self.virtual_liquidity,
+            weights: &self.weights,
+            outcome_names: &self.outcome_names,
+            min_bet: self.min_bet,
+            max_bet: self.max_bet,
+            polymarket_id: &self.polymarket_id,
+            metadata_url: &self.metadata_url,
+            kind: &self.kind,
+            pricing: &self.pricing,
+        }, NOW)
+    }
+}
+
+fn rejects(args: Args, expected: PolybetError) {
+    assert_eq!(args.check().unwrap_err(), error!(expected));
+}
+
+#[test]
+fn accepts_valid_markets() {
+    assert!(Args::binary().check().is_ok());
+    assert!(Args::with_outcomes(8).check().is_ok());
+
+    let mut lmsr = Args::with_outcomes(4);
+    lmsr.pricing = PricingMode::Lmsr { b: 1_000_000 };
+    assert!(lmsr.check().is_ok());
+}
+
+#[test]
+fn rejects_outcomes_count_out_of_range() {
+    for count in [0, 1, 9, u8::MAX] {
+        rejects(Args::with_outcomes(count), PolybetError::InvalidOutcomesCount);
+    }
+}
+
+#[test]
+fn rejects_question_outside_seed_limit() {
+    let mut args = Args::binary();
+    args.question = "q".repeat(32);
+    assert!(args.check().is_ok());
+
+    for question in [String::new(), "q".repeat(33), "q".repeat(64)] {
+        let mut args = Args::binary();
+        args.question = question;
+        rejects(args, PolybetError::InvalidQuestion);
+    }
+}
+
+#[test]
+fn rejects_end_timestamp_not_in_future() {
+    for end_timestamp in [NOW - 1, NOW, 0, i64::MIN] {
+        let mut args = Args::binary();
+        args.end_timestamp = end_timestamp;
+        rejects(args, PolybetError::EndTimestampInPast);
+    }
+}
+
+#[test]
+fn rejects_weight_sum_overflow() {
+    let mut args = Args::binary();
+    args.weights[..2].copy_from_slice(&[u32::MAX, 1]);
+    rejects(args, PolybetError::InvalidWeights);
+}
+
+#[test]
+fn rejects_liquidity_without_weights() {
+    let mut args = Args::binary();
+    args.weights = [0; 8];
+    rejects(args, PolybetError::InvalidWeights);
+
+    // Weights past `outcomes_count` don't count
+    let mut args = Args::binary();
+    args.weights = [0, 0, 5, 0, 0, 0, 0, 0];
+    rejects(args, PolybetError::InvalidWeights);
+
+    let mut args = Args::binary();
+    args.weights = [0; 8];
+    args.virtual_liquidity = 0;
+    assert!(args.check().is_ok());
+}
+
+#[test]
+fn rejects_bad_bet_limits() {
+    let mut args = Args::binary();
+    args.min_bet = 0;
+    rejects(args, PolybetError::InvalidBetLimits);
+
+    let mut args = Args::binary();
+    args.min_bet = 10;
+    args.max_bet = 9;
+    rejects(args, PolybetError::InvalidBetLimits);
+}
+
+#[test]
+fn rejects_outcome_names_mismatch() {
+    let mut args = Args::binary();
+    args.outcome_names.pop();
+    rejects(args, PolybetError::OutcomeNamesMismatch);
+
+    let mut args = Args::binary();
+    args.outcome_names[1] = "N".repeat(33);
+    rejects(args, PolybetError::OutcomeNameTooLong);
+}
+
+#[test]
+fn rejects_oversized_metadata() {
+    let mut args = Args::binary();
+    args.polymarket_id = "0".repeat(67);
+    rejects(args, PolybetError::PolymarketIdTooLong);
+
+    let mut args = Args::binary();
+    args.metadata_url = "u".repeat(201);
+    rejects(args, PolybetError::MetadataUrlTooLong);
+}
+
+#[test]
+fn rejects_bad_pricing() {
+    let mut args = Args::binary();
+    args.pricing = PricingMode::Lmsr { b: 0 };
+    rejects(args, PolybetError::InvalidLiquidityParameter);
+
+    let mut args = Args::binary();
+    args.pricing = PricingMode::Parimutuel;
+    rejects(args, PolybetError::InvalidLiquidityParameter);
+}
+
+#[test]
+fn rejects_bad_price_markets() {
+    let kind = |settlement_timestamp| MarketKind::PriceThreshold {
+        feed_id: [7; 32],
+        strike: 200,
+        expo: 0,
+        comparison: Comparison::Above,
+        settlement_timestamp,
+    };
+
+    let mut args = Args::with_outcomes(3);
+    args.kind = kind(NOW + 86_400);
+    rejects(args, PolybetError::InvalidPriceMarket);
+
+    let mut args = Args::binary();
+    args.kind = kind(NOW + 60);
+    rejects(args, PolybetError::InvalidPriceMarket);
+}
+
+#[test]
+fn winner_index_must_be_an_outcome() {
+    assert!(check_winner(2, 0).is_ok());
+    assert!(check_winner(2, 1).is_ok());
+    assert!(check_winner(8, 7).is_ok());
+
+    for (outcomes_count, winner_index) in [(2, 2), (2, 8), (8, 8), (3, u8::MAX)] {
+        assert_eq!(check_winner(outcomes_count, winner_index).unwrap_err(), error!(PolybetError::InvalidWinnerIndex));
+    }
+}
+
+#[test]
+fn vote_outcome_must_exist() {
+    assert!(check_outcome(2, 1).is_ok());
+    assert!(check_outcome(8, 7).is_ok());
+
+    for (outcomes_count, outcome_index) in [(2, 2), (2, 7), (8, 8), (3, u8::MAX)] {
+        assert_eq!(check_outcome(outcomes_count, outcome_index).unwrap_err(), error!(PolybetError::InvalidOutcomeIndex));
+    }
+}
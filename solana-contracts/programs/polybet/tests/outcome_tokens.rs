@@ -0,0 +1,218 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::program_error::ProgramError;
+use common::*;
+use polybet::PolybetError;
+
+/// A market with both outcome mints created
+struct Tokens {
+    ledger: Ledger,
+    mints: [AccountInfo<'static>; 2],
+}
+
+/// A holder's collateral and one token account per outcome
+struct Holder {
+    wallet: Wallet,
+    outcomes: [AccountInfo<'static>; 2],
+}
+
+fn create_outcome_mint(ledger: &Ledger, outcome_index: u8) -> std::result::Result<AccountInfo<'static>, ProgramError> {
+    let mint = uninitialized(pda(&[b"outcome_mint", ledger.market.key.as_ref(), &[outcome_index]]).0);
+    let accounts = [
+        ledger.market.clone(),
+        mint.clone(),
+        ledger.config.clone(),
+        ledger.mint.clone(),
+        ledger.creator.signer.clone(),
+        system_program(),
+        token_program(),
+    ];
+    run(polybet::instruction::CreateOutcomeMint { outcome_index }, &accounts).map(|_| mint)
+}
+
+impl Tokens {
+    fn new(f: &Fixture) -> Self {
+        let ledger = f.ledger(&f.open_market());
+        let mints = [create_outcome_mint(&ledger, 0).unwrap(), create_outcome_mint(&ledger, 1).unwrap()];
+        Tokens { ledger, mints }
+    }
+
+    fn holder(&self, collateral: u64) -> Holder {
+        let wallet = self.ledger.wallet(collateral);
+        let outcomes = self.mints.clone().map(|mint| token_account(Pubkey::new_unique(), mint.key(), wallet.key(), 0));
+        Holder { wallet, outcomes }
+    }
+
+    fn complete_set_accounts(&self, holder: &Holder) -> Vec<AccountInfo<'static>> {
+        let mut accounts = vec![
+            self.ledger.config.clone(),
+            self.ledger.mint.clone(),
+            self.ledger.market.clone(),
+            self.ledger.market_vault.clone(),
+            holder.wallet.token.clone(),
+            holder.wallet.signer.clone(),
+            token_program(),
+        ];
+        for (mint, outcome_token) in self.mints.iter().zip(&holder.outcomes) {
+            accounts.extend([mint.clone(), outcome_token.clone()]);
+        }
+        accounts
+    }
+
+    fn mint_complete_set(&self, holder: &Holder, amount: u64) -> ProgramResult {
+        run(polybet::instruction::MintCompleteSet { amount }, &self.complete_set_accounts(holder))
+    }
+
+    fn redeem_complete_set(&self, holder: &Holder, amount: u64) -> ProgramResult {
+        run(polybet::instruction::RedeemCompleteSet { amount }, &self.complete_set_accounts(holder))
+    }
+
+    fn redeem_winning(&self, holder: &Holder, outcome_index: usize, amount: u64) -> ProgramResult {
+        let accounts = [
+            self.ledger.config.clone(),
+            self.ledger.mint.clone(),
+            self.ledger.market.clone(),
+            self.mints[outcome_index].clone(),
+            holder.outcomes[outcome_index].clone(),
+            self.ledger.market_vault.clone(),
+            holder.wallet.token.clone(),
+            holder.wallet.signer.clone(),
+            token_program(),
+        ];
+        run(polybet::instruction::RedeemWinning { amount }, &accounts)
+    }
+
+    fn redeem_cancelled(&self, holder: &Holder, outcome_index: u8, amount: u64) -> ProgramResult {
+        let accounts = [
+            self.ledger.config.clone(),
+            self.ledger.mint.clone(),
+            self.ledger.market.clone(),
+            self.mints[outcome_index as usize].clone(),
+            holder.outcomes[outcome_index as usize].clone(),
+            self.ledger.market_vault.clone(),
+            holder.wallet.token.clone(),
+            holder.wallet.signer.clone(),
+            token_program(),
+        ];
+        run(polybet::instruction::RedeemCancelled { outcome_index, amount }, &accounts)
+    }
+}
+
+#[test]
+fn outcome_mints_are_created_in_order() {
+    let f = Fixture::new();
+    let ledger = f.ledger(&f.open_market());
+    let err = create_outcome_mint(&ledger, 1).unwrap_err();
+    assert_eq!(err, error!(PolybetError::InvalidOutcomeMint).into());
+
+    let mint = create_outcome_mint(&ledger, 0).unwrap();
+    assert_eq!(*mint.owner, TOKEN_2022_ID);
+    assert_eq!(ledger.market().outcome_mints_created, 1);
+}
+
+#[test]
+fn complete_sets_wait_for_every_outcome_mint() {
+    let f = Fixture::new();
+    let ledger = f.ledger(&f.open_market());
+    let mint = create_outcome_mint(&ledger, 0).unwrap();
+    let user = ledger.wallet(1_000);
+    let accounts = [
+        ledger.config.clone(),
+        ledger.mint.clone(),
+        ledger.market.clone(),
+        ledger.market_vault.clone(),
+        user.token.clone(),
+        user.signer.clone(),
+        token_program(),
+        mint.clone(),
+        token_account(Pubkey::new_unique(), mint.key(), user.key(), 0),
+    ];
+    let err = run(polybet::instruction::MintCompleteSet { amount: 100 }, &accounts).unwrap_err();
+    assert_eq!(err, error!(PolybetError::OutcomeTokensDisabled).into());
+}
+
+#[test]
+fn complete_set_round_trip_conserves_collateral() {
+    let f = Fixture::new();
+    let tokens = Tokens::new(&f);
+    let holder = tokens.holder(1_000);
+
+    tokens.mint_complete_set(&holder, 400).unwrap();
+    assert_eq!(token_amount(&holder.wallet.token), 600);
+    assert_eq!(token_amount(&tokens.ledger.market_vault), 400);
+    assert_eq!(holder.outcomes.each_ref().map(token_amount), [400, 400]);
+    assert_eq!(tokens.ledger.market().complete_sets, 400);
+
+    tokens.redeem_complete_set(&holder, 400).unwrap();
+    assert_eq!(token_amount(&holder.wallet.token), 1_000);
+    assert_eq!(token_amount(&tokens.ledger.market_vault), 0);
+    assert_eq!(tokens.mints.each_ref().map(mint_supply), [0, 0]);
+    assert_eq!(tokens.ledger.market().complete_sets, 0);
+}
+
+#[test]
+fn winning_tokens_redeem_one_for_one_and_losing_tokens_are_rejected() {
+    let f = Fixture::new();
+    let tokens = Tokens::new(&f);
+    let holder = tokens.holder(1_000);
+    tokens.mint_complete_set(&holder, 400).unwrap();
+    tokens.ledger.resolve(0).unwrap();
+
+    let err = tokens.redeem_winning(&holder, 1, 400).unwrap_err();
+    assert_eq!(err, error!(PolybetError::InvalidOutcomeMint).into());
+    assert_eq!(token_amount(&holder.outcomes[1]), 400);
+
+    tokens.redeem_winning(&holder, 0, 400).unwrap();
+    assert_eq!(token_amount(&holder.wallet.token), 1_000);
+    assert_eq!(token_amount(&holder.outcomes[0]), 0);
+    assert_eq!(token_amount(&tokens.ledger.market_vault), 0);
+    assert_eq!(tokens.ledger.market().complete_sets, 0);
+}
+
+#[test]
+fn cancelled_market_redeems_any_outcome_at_one_over_n() {
+    let f = Fixture::new();
+    let tokens = Tokens::new(&f);
+    let holder = tokens.holder(1_000);
+    tokens.mint_complete_set(&holder, 400).unwrap();
+
+    let err = tokens.redeem_cancelled(&holder, 0, 400).unwrap_err();
+    assert_eq!(err, error!(PolybetError::MarketNotCancelled).into());
+
+    tokens.ledger.cancel(&tokens.ledger.creator.signer).unwrap();
+    // Neither side won: each token is half of the collateral behind a set
+    tokens.redeem_cancelled(&holder, 0, 400).unwrap();
+    assert_eq!(token_amount(&holder.wallet.token), 800);
+    tokens.redeem_cancelled(&holder, 1, 400).unwrap();
+    assert_eq!(token_amount(&holder.wallet.token), 1_000);
+    assert_eq!(tokens.mints.each_ref().map(mint_supply), [0, 0]);
+    assert_eq!(token_amount(&tokens.ledger.market_vault), 0);
+    assert_eq!(tokens.ledger.market().complete_sets, 0);
+}
+
+#[test]
+fn cancelled_redemption_checks_the_outcome_mint() {
+    let f = Fixture::new();
+    let tokens = Tokens::new(&f);
+    let holder = tokens.holder(1_000);
+    tokens.mint_complete_set(&holder, 400).unwrap();
+    tokens.ledger.cancel(&tokens.ledger.creator.signer).unwrap();
+
+    // Outcome 1's tokens presented as outcome 0
+    let accounts = [
+        tokens.ledger.config.clone(),
+        tokens.ledger.mint.clone(),
+        tokens.ledger.market.clone(),
+        tokens.mints[1].clone(),
+        holder.outcomes[1].clone(),
+        tokens.ledger.market_vault.clone(),
+        holder.wallet.token.clone(),
+        holder.wallet.signer.clone(),
+        token_program(),
+    ];
+    let err = run(polybet::instruction::RedeemCancelled { outcome_index: 0, amount: 400 }, &accounts).unwrap_err();
+    assert_eq!(err, error!(PolybetError::InvalidOutcomeMint).into());
+    assert_eq!(token_amount(&holder.wallet.token), 600);
+}
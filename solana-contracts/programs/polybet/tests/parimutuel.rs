@@ -0,0 +1,75 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::*;
+use polybet::{PolybetError, PricingMode, ProtocolConfig};
+
+/// An empty pari-mutuel pool, nothing seeded
+fn pool(f: &Fixture) -> Ledger {
+    let mut market = f.open_market();
+    market.pricing = PricingMode::Parimutuel;
+    market.total_pot = 0;
+    market.outcome_totals = [0; 8];
+    f.ledger(&market)
+}
+
+#[test]
+fn claim_uses_fee_from_market_creation() {
+    let f = Fixture::new();
+    let ledger = pool(&f);
+    let (winner, loser) = (ledger.wallet(1_000), ledger.wallet(1_000));
+    let vote = ledger.place_vote(&winner, 0, 0, 1_000).unwrap();
+    ledger.place_vote(&loser, 0, 1, 1_000).unwrap();
+
+    // Raising the fee afterwards doesn't reach pools that are already open
+    let mut config: ProtocolConfig = read(&ledger.config);
+    config.protocol_fee_bps = 2_000;
+    ledger.set_config(&config);
+
+    ledger.resolve(0).unwrap();
+    ledger.claim_winnings(&winner, &vote).unwrap();
+    assert_eq!(token_amount(&winner.token), 1_800);
+    assert_eq!(token_amount(&ledger.fee_vault), 200);
+}
+
+#[test]
+fn winners_split_the_pool_pro_rata() {
+    let f = Fixture::new();
+    let ledger = pool(&f);
+    let (small, large, loser) = (ledger.wallet(1_000), ledger.wallet(3_000), ledger.wallet(2_000));
+    let small_vote = ledger.place_vote(&small, 0, 0, 1_000).unwrap();
+    let large_vote = ledger.place_vote(&large, 0, 0, 3_000).unwrap();
+    let losing_vote = ledger.place_vote(&loser, 0, 1, 2_000).unwrap();
+    ledger.resolve(0).unwrap();
+
+    let err = ledger.claim_winnings(&loser, &losing_vote).unwrap_err();
+    assert_eq!(err, error!(PolybetError::InvalidOutcome).into());
+
+    // 6,000 pool less the 10% fee, split 1:3
+    ledger.claim_winnings(&small, &small_vote).unwrap();
+    ledger.claim_winnings(&large, &large_vote).unwrap();
+    assert_eq!(token_amount(&small.token), 1_350);
+    assert_eq!(token_amount(&large.token), 4_050);
+    assert_eq!(token_amount(&ledger.fee_vault), 600);
+    assert_eq!(token_amount(&ledger.market_vault), 0);
+}
+
+#[test]
+fn rounding_never_pays_out_more_than_the_pool() {
+    let f = Fixture::new();
+    let ledger = pool(&f);
+    let winners = [ledger.wallet(1), ledger.wallet(2), ledger.wallet(4)];
+    let votes: Vec<_> = winners.iter().zip([1, 2, 4]).map(|(wallet, amount)| ledger.place_vote(wallet, 0, 0, amount).unwrap()).collect();
+    ledger.place_vote(&ledger.wallet(6), 0, 1, 6).unwrap();
+    ledger.resolve(0).unwrap();
+
+    for (wallet, vote) in winners.iter().zip(&votes) {
+        ledger.claim_winnings(wallet, vote).unwrap();
+    }
+    let paid: u64 = winners.iter().map(|wallet| token_amount(&wallet.token)).sum::<u64>() + token_amount(&ledger.fee_vault);
+    assert!(paid <= 13);
+    // Whatever rounding leaves behind is dust, not a missing claim
+    assert_eq!(paid + token_amount(&ledger.market_vault), 13);
+    assert!(token_amount(&ledger.market_vault) < winners.len() as u64 * 2);
+    assert_eq!(ledger.market().liabilities(), Some(0));
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::*;
+use polybet::{PolybetError, Vote};
+
+#[test]
+fn vote_escrows_stake_and_locks_payout() {
+    let f = Fixture::new();
+    let ledger = f.ledger(&f.open_market());
+    let user = ledger.wallet(1_000);
+
+    let vote = ledger.place_vote(&user, 0, 0, 1_000).unwrap();
+    assert_eq!(token_amount(&user.token), 0);
+    assert_eq!(token_amount(&ledger.market_vault), 1_000);
+
+    // 1,000 at even odds, 10% protocol fee: 1,000 * 0.9 * 2,000 / 1,000
+    let vote: Vote = read(&vote);
+    assert_eq!((vote.locked_payout, vote.locked_dev_fee), (1_800, 180));
+    let market = ledger.market();
+    assert_eq!(market.deposits, 1_000);
+    assert_eq!(market.payouts_owed[0], 1_980);
+    assert_eq!(market.outcome_totals[..2], [2_000, 1_000]);
+}
+
+#[test]
+fn vote_rejects_market_resolved_early() {
+    let f = Fixture::new();
+    let ledger = f.ledger(&f.open_market());
+    // The oracle settled before `end_timestamp`: the winner is already known
+    ledger.resolve(0).unwrap();
+
+    let err = ledger.place_vote(&ledger.wallet(1_000), 0, 0, 1_000).unwrap_err();
+    assert_eq!(err, error!(PolybetError::AlreadyResolved).into());
+}
+
+#[test]
+fn vote_rejects_pending_resolution() {
+    let f = Fixture::new();
+    let mut market = f.open_market();
+    market.pending_resolution = true;
+    let ledger = f.ledger(&market);
+
+    let err = ledger.place_vote(&ledger.wallet(1_000), 0, 0, 1_000).unwrap_err();
+    assert_eq!(err, error!(PolybetError::AlreadyResolved).into());
+}
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use polybet::pyth::{settle_threshold, PriceFeedMessage, PriceUpdateV2, VerificationLevel, PRICE_UPDATE_V2_DISCRIMINATOR};
+use polybet::{Comparison, PolybetError};
+
+const SETTLEMENT: i64 = 1_750_000_000;
+const EXPO: i32 = -8;
+const FEED: [u8; 32] = [7; 32];
+
+/// SOL/USD at $150.00 with a $0.10 confidence interval, the first update after settlement (5s in)
+fn message() -> PriceFeedMessage {
+    PriceFeedMessage {
+        feed_id: FEED,
+        price: 150_0000_0000,
+        conf: 1000_0000,
+        exponent: EXPO,
+        publish_time: SETTLEMENT + 5,
+        prev_publish_time: SETTLEMENT - 1,
+        ema_price: 149_0000_0000,
+        ema_conf: 1000_0000,
+    }
+}
+
+fn fixture(update: &PriceUpdateV2) -> Vec<u8> {
+    let mut data = PRICE_UPDATE_V2_DISCRIMINATOR.to_vec();
+    update.serialize(&mut data).unwrap();
+    data
+}
+
+fn update(verification_level: VerificationLevel) -> PriceUpdateV2 {
+    PriceUpdateV2 {
+        write_authority: Pubkey::new_unique(),
+        verification_level,
+        price_message: message(),
+        posted_slot: 42,
+    }
+}
+
+#[test]
+fn parses_price_account_fixture() {
+    let update = update(VerificationLevel::Full);
+    let parsed = PriceUpdateV2::try_from_slice_checked(&fixture(&update)).unwrap();
+    assert_eq!(parsed, update);
+}
+
+#[test]
+fn rejects_wrong_discriminator() {
+    let mut data = fixture(&update(VerificationLevel::Full));
+    data[0] ^= 0xff;
+    assert_eq!(PriceUpdateV2::try_from_slice_checked(&data).unwrap_err(), error!(PolybetError::InvalidPriceFeed));
+}
+
+#[test]
+fn rejects_truncated_account() {
+    let data = fixture(&update(VerificationLevel::Full));
+    assert_eq!(PriceUpdateV2::try_from_slice_checked(&data[..60]).unwrap_err(), error!(PolybetError::InvalidPriceFeed));
+}
+
+#[test]
+fn rejects_partially_verified_update() {
+    let data = fixture(&update(VerificationLevel::Partial { num_signatures: 5 }));
+    assert_eq!(PriceUpdateV2::try_from_slice_checked(&data).unwrap_err(), error!(PolybetError::InvalidPriceFeed));
+}
+
+#[test]
+fn settles_above_and_below() {
+    let now = SETTLEMENT + 30;
+    assert_eq!(settle_threshold(&message(), &FEED, 140_0000_0000, EXPO, Comparison::Above, SETTLEMENT, now).unwrap(), 0);
+    assert_eq!(settle_threshold(&message(), &FEED, 160_0000_0000, EXPO, Comparison::Above, SETTLEMENT, now).unwrap(), 1);
+    assert_eq!(settle_threshold(&message(), &FEED, 160_0000_0000, EXPO, Comparison::Below, SETTLEMENT, now).unwrap(), 0);
+    assert_eq!(settle_threshold(&message(), &FEED, 140_0000_0000, EXPO, Comparison::Below, SETTLEMENT, now).unwrap(), 1);
+    // Landing exactly on the strike does not satisfy a strict comparison
+    assert_eq!(settle_threshold(&message(), &FEED, 150_0000_0000, EXPO, Comparison::Above, SETTLEMENT, now).unwrap(), 1);
+}
+
+#[test]
+fn rejects_before_settlement() {
+    let err = settle_threshold(&message(), &FEED, 140_0000_0000, EXPO, Comparison::Above, SETTLEMENT, SETTLEMENT - 1).unwrap_err();
+    assert_eq!(err, error!(PolybetError::SettlementNotReached));
+}
+
+#[test]
+fn rejects_price_published_before_settlement() {
+    let mut message = message();
+    message.publish_time = SETTLEMENT - 1;
+    message.prev_publish_time = SETTLEMENT - 2;
+    let err = settle_threshold(&message, &FEED, 140_0000_0000, EXPO, Comparison::Above, SETTLEMENT, SETTLEMENT + 30).unwrap_err();
+    assert_eq!(err, error!(PolybetError::StalePrice));
+}
+
+#[test]
+fn settles_on_update_published_exactly_at_settlement() {
+    let mut message = message();
+    message.publish_time = SETTLEMENT;
+    assert_eq!(settle_threshold(&message, &FEED, 140_0000_0000, EXPO, Comparison::Above, SETTLEMENT, SETTLEMENT + 30).unwrap(), 0);
+}
+
+#[test]
+fn rejects_later_update_than_the_first_after_settlement() {
+    // A valid, fresh price, but an earlier update already covered the settlement time
+    let mut message = message();
+    message.prev_publish_time = SETTLEMENT;
+    let err = settle_threshold(&message, &FEED, 140_0000_0000, EXPO, Comparison::Above, SETTLEMENT, SETTLEMENT + 30).unwrap_err();
+    assert_eq!(err, error!(PolybetError::StalePrice));
+}
+
+#[test]
+fn rejects_price_published_too_long_after_settlement() {
+    let mut message = message();
+    message.publish_time = SETTLEMENT + polybet::pyth::MAX_PRICE_AGE + 1;
+    let err = settle_threshold(&message, &FEED, 140_0000_0000, EXPO, Comparison::Above, SETTLEMENT, SETTLEMENT + 120).unwrap_err();
+    assert_eq!(err, error!(PolybetError::StalePrice));
+}
+
+#[test]
+fn rejects_wide_confidence() {
+    let mut message = message();
+    message.conf = 2_0000_0000; // $2 on $150 is above the 1% ceiling
+    let err = settle_threshold(&message, &FEED, 140_0000_0000, EXPO, Comparison::Above, SETTLEMENT, SETTLEMENT + 30).unwrap_err();
+    assert_eq!(err, error!(PolybetError::PriceTooUncertain));
+}
+
+#[test]
+fn rejects_exponent_mismatch() {
+    let err = settle_threshold(&message(), &FEED, 140_0000, -6, Comparison::Above, SETTLEMENT, SETTLEMENT + 30).unwrap_err();
+    assert_eq!(err, error!(PolybetError::InvalidPriceFeed));
+}
+
+#[test]
+fn rejects_non_positive_price() {
+    let mut message = message();
+    message.price = 0;
+    let err = settle_threshold(&message, &FEED, 140_0000_0000, EXPO, Comparison::Above, SETTLEMENT, SETTLEMENT + 30).unwrap_err();
+    assert_eq!(err, error!(PolybetError::InvalidPriceFeed));
+}
+
+#[test]
+fn rejects_update_for_another_feed() {
+    let err = settle_threshold(&message(), &[8; 32], 140_0000_0000, EXPO, Comparison::Above, SETTLEMENT, SETTLEMENT + 30).unwrap_err();
+    assert_eq!(err, error!(PolybetError::InvalidPriceFeed));
+}
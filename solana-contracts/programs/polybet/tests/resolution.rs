@@ -0,0 +1,66 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use common::*;
+use polybet::{MarketKind, PolybetError, ResolveMarket, ResolveViaOracle, Role};
+
+fn market_account(creator: Pubkey, kind: MarketKind) -> AccountInfo<'static> {
+    let key = Pubkey::new_unique();
+    let mut market = market(key, creator);
+    market.kind = kind;
+    program_account(key, &market)
+}
+
+#[test]
+fn optimistic_market_rejects_instant_resolution() {
+    let (creator, resolver) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let err = validate::<ResolveMarket>(vec![market_account(creator, MarketKind::Optimistic), signer(creator), absent()]).unwrap_err();
+    assert_eq!(err, error!(PolybetError::OptimisticMarket));
+
+    let accounts = vec![market_account(creator, MarketKind::Optimistic), signer(resolver), role(Role::Resolver, resolver)];
+    let err = validate::<ResolveMarket>(accounts).unwrap_err();
+    assert_eq!(err, error!(PolybetError::OptimisticMarket));
+}
+
+#[test]
+fn optimistic_market_rejects_oracle_resolution() {
+    // The fixture's oracle is the creator
+    let creator = Pubkey::new_unique();
+    assert!(validate::<ResolveViaOracle>(vec![market_account(creator, MarketKind::Standard), signer(creator)]).is_ok());
+
+    let err = validate::<ResolveViaOracle>(vec![market_account(creator, MarketKind::Optimistic), signer(creator)]).unwrap_err();
+    assert_eq!(err, error!(PolybetError::OptimisticMarket));
+}
+
+fn propose_resolution(ledger: &Ledger, proposer: &Wallet, winner_index: u8) -> ProgramResult {
+    let accounts = [
+        ledger.config.clone(),
+        ledger.mint.clone(),
+        ledger.market.clone(),
+        uninitialized(pda(&[b"resolution", ledger.market.key.as_ref()]).0),
+        ledger.treasury_vault.clone(),
+        proposer.token.clone(),
+        proposer.signer.clone(),
+        system_program(),
+        token_program(),
+    ];
+    run(polybet::instruction::ProposeResolution { winner_index }, &accounts)
+}
+
+#[test]
+fn only_optimistic_markets_take_proposals() {
+    let f = Fixture::new();
+    set_now(1_000);
+
+    let ledger = f.ledger(&f.open_market());
+    let err = propose_resolution(&ledger, &ledger.wallet(0), 0).unwrap_err();
+    assert_eq!(err, error!(PolybetError::NotOptimisticMarket).into());
+    assert!(!ledger.market().pending_resolution);
+
+    let mut market = f.open_market();
+    market.kind = MarketKind::Optimistic;
+    let ledger = f.ledger(&market);
+    propose_resolution(&ledger, &ledger.wallet(0), 0).unwrap();
+    assert!(ledger.market().pending_resolution);
+}
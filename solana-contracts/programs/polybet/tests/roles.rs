@@ -0,0 +1,79 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+use common::*;
+use polybet::{PolybetError, ResolveMarket, RevokeRole, Role, SetProtocolPaused};
+
+fn pause_accounts(admin: Pubkey, signer_key: Pubkey, role_account: AccountInfo<'static>) -> Vec<AccountInfo<'static>> {
+    vec![
+        program_account(pda(&[b"config"]).0, &config(admin, Pubkey::new_unique(), Pubkey::new_unique())),
+        signer(signer_key),
+        role_account,
+    ]
+}
+
+fn resolve_accounts(creator: Pubkey, signer_key: Pubkey, role_account: AccountInfo<'static>) -> Vec<AccountInfo<'static>> {
+    let key = Pubkey::new_unique();
+    vec![program_account(key, &market(key, creator)), signer(signer_key), role_account]
+}
+
+#[test]
+fn master_authority_needs_no_role() {
+    let admin = Pubkey::new_unique();
+    assert!(validate::<SetProtocolPaused>(pause_accounts(admin, admin, absent())).is_ok());
+}
+
+#[test]
+fn pauser_can_pause() {
+    let ops = Pubkey::new_unique();
+    assert!(validate::<SetProtocolPaused>(pause_accounts(Pubkey::new_unique(), ops, role(Role::Pauser, ops))).is_ok());
+}
+
+#[test]
+fn pause_rejects_signer_without_role() {
+    let err = validate::<SetProtocolPaused>(pause_accounts(Pubkey::new_unique(), Pubkey::new_unique(), absent())).unwrap_err();
+    assert_eq!(err, error!(PolybetError::Unauthorized));
+}
+
+#[test]
+fn pause_rejects_other_role() {
+    let ops = Pubkey::new_unique();
+    let err = validate::<SetProtocolPaused>(pause_accounts(Pubkey::new_unique(), ops, role(Role::Treasurer, ops))).unwrap_err();
+    assert_eq!(err, error!(ErrorCode::ConstraintSeeds));
+}
+
+#[test]
+fn pause_rejects_someone_elses_role() {
+    let ops = Pubkey::new_unique();
+    let err = validate::<SetProtocolPaused>(pause_accounts(Pubkey::new_unique(), ops, role(Role::Pauser, Pubkey::new_unique()))).unwrap_err();
+    assert_eq!(err, error!(ErrorCode::ConstraintSeeds));
+}
+
+#[test]
+fn resolver_or_creator_can_resolve() {
+    let (creator, resolver) = (Pubkey::new_unique(), Pubkey::new_unique());
+    assert!(validate::<ResolveMarket>(resolve_accounts(creator, creator, absent())).is_ok());
+    assert!(validate::<ResolveMarket>(resolve_accounts(creator, resolver, role(Role::Resolver, resolver))).is_ok());
+
+    let err = validate::<ResolveMarket>(resolve_accounts(creator, resolver, absent())).unwrap_err();
+    assert_eq!(err, error!(PolybetError::Unauthorized));
+}
+
+#[test]
+fn admin_revokes_roles() {
+    let (master, admin, ops) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let accounts = |signer_key, role_account| {
+        vec![
+            program_account(pda(&[b"config"]).0, &config(master, Pubkey::new_unique(), Pubkey::new_unique())),
+            role(Role::Pauser, ops),
+            signer(signer_key),
+            role_account,
+        ]
+    };
+    assert!(validate::<RevokeRole>(accounts(admin, role(Role::Admin, admin))).is_ok());
+
+    // A role cannot manage the registry unless it is Admin
+    let err = validate::<RevokeRole>(accounts(ops, role(Role::Pauser, ops))).unwrap_err();
+    assert_eq!(err, error!(ErrorCode::ConstraintSeeds));
+}
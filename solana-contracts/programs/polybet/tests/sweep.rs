@@ -0,0 +1,55 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use common::*;
+use polybet::PolybetError;
+
+fn sweep_market_profit(ledger: &Ledger, destination: &AccountInfo<'static>, amount: u64) -> ProgramResult {
+    let accounts = [
+        ledger.config.clone(),
+        ledger.mint.clone(),
+        ledger.market.clone(),
+        ledger.market_vault.clone(),
+        destination.clone(),
+        ledger.creator.signer.clone(),
+        absent(),
+        token_program(),
+    ];
+    run(polybet::instruction::SweepMarketProfit { amount }, &accounts)
+}
+
+/// Resolved NO with one losing YES vote of 1,000 left in the vault
+fn settled(f: &Fixture) -> Ledger {
+    let ledger = f.ledger(&f.open_market());
+    ledger.place_vote(&ledger.wallet(1_000), 0, 0, 1_000).unwrap();
+    ledger.resolve(1).unwrap();
+    ledger
+}
+
+#[test]
+fn sweeps_only_the_surplus() {
+    let f = Fixture::new();
+    let ledger = settled(&f);
+    let treasury = ledger.wallet(0);
+
+    let err = sweep_market_profit(&ledger, &treasury.token, 1_001).unwrap_err();
+    assert_eq!(err, error!(PolybetError::InsufficientSurplus).into());
+
+    sweep_market_profit(&ledger, &treasury.token, 1_000).unwrap();
+    assert_eq!(token_amount(&treasury.token), 1_000);
+    assert_eq!(token_amount(&ledger.market_vault), 0);
+}
+
+#[test]
+fn paused_market_cannot_be_swept() {
+    let f = Fixture::new();
+    let ledger = settled(&f);
+    let mut market = ledger.market();
+    market.paused = true;
+    ledger.set_market(&market);
+
+    let err = sweep_market_profit(&ledger, &ledger.wallet(0).token, 1_000).unwrap_err();
+    assert_eq!(err, error!(PolybetError::Paused).into());
+    assert_eq!(token_amount(&ledger.market_vault), 1_000);
+}
@@ -30,7 +30,11 @@ import {
     initializeProtocol,
     initializeMarketOnChain,
     getTreasuryVaultPDA,
-    BETTING_MINT
+    getATA,
+    BETTING_MINT,
+    TREASURY_WALLET,
+    RESOLUTION_BOND,
+    DISPUTE_WINDOW_SECONDS
 } from '@/services/web3';
 import { dailyPredictions } from '@/data/predictions';
 import { fetchDailyMarkets } from '@/services/polymarket';
@@ -123,7 +127,9 @@ export default function AdminPage() {
     const handleInitializeProtocol = async () => {
         if (!publicKey) return;
         try {
-            const tx = await initializeProtocol(window.solana, BETTING_MINT);
+            // Protocol fees go to the treasury wallet's token account for the betting mint
+            const feeRecipient = await getATA(TREASURY_WALLET, BETTING_MINT);
+            const tx = await initializeProtocol(window.solana, BETTING_MINT, feeRecipient, RESOLUTION_BOND, DISPUTE_WINDOW_SECONDS);
             alert("Protocol Initialized! TX: " + tx);
             checkProtocol();
         } catch (e: any) {
@@ -160,7 +166,9 @@ export default function AdminPage() {
                 event.endTime,
                 event.outcomes.length,
                 1000000,
-                finalWeights as [number, number, number, number, number, number, number, number]
+                finalWeights as [number, number, number, number, number, number, number, number],
+                event.outcomes,
+                event.polymarketId ?? ""
             );
             alert("Daily Market Mirrored with Fixed Odds! TX: " + tx);
         } catch (e: any) {
@@ -755,30 +755,36 @@ export const PredictionCard = ({
 
                             const toastId = toast.loading("Processing claim...");
                             try {
-                                const { getProgram, getMarketPDA, getVotePDA, getConfigPDA, getATA, BETTING_MINT, TOKEN_PROGRAM_ID } = await import('@/services/web3');
+                                const { getProgram, getUserVotes, getConfigPDA, getMarketVaultPDA, getATA, BETTING_MINT, TOKEN_PROGRAM_ID } = await import('@/services/web3');
                                 const program = getProgram({ publicKey, signTransaction, signAllTransactions });
                                 if (!program) throw new Error("Program not initialized");
 
                                 const marketPda = new PublicKey(marketPublicKey);
-                                const votePda = (await getVotePDA(marketPda, publicKey))[0];
                                 const configPda = (await getConfigPDA());
-                                const configAccount: any = await program.account.globalConfig.fetch(configPda);
+                                const configAccount: any = await (program.account as any).protocolConfig.fetch(configPda);
+                                const marketAccount: any = await (program.account as any).market.fetch(marketPda);
 
-                                const vaultTokenAcc = (await getATA(marketPda, BETTING_MINT));
+                                // Each bet is its own vote PDA: claim every unclaimed winning one
+                                const winning = (await getUserVotes(program, marketPda, publicKey))
+                                    .filter((v: any) => !v.account.claimed && v.account.outcomeIndex === marketAccount.winnerIndex);
+                                if (winning.length === 0) throw new Error("No winning positions to claim");
+
+                                const marketVault = await getMarketVaultPDA(marketPda);
                                 const userTokenAcc = (await getATA(publicKey, BETTING_MINT));
-                                const devVault = configAccount.devVault || new PublicKey('2KF9SAvpU2h2ZhczzMLbgx7arkjG8QHCXbQ6XaDqtEtm'); // Fallback to personal wallet
-                                const devTokenAcc = (await getATA(devVault, BETTING_MINT));
-
-                                await program.methods.claimWinnings().accounts({
-                                    market: marketPda,
-                                    config: configPda,
-                                    vote: votePda,
-                                    user: publicKey,
-                                    userToken: userTokenAcc,
-                                    devToken: devTokenAcc,
-                                    treasuryVault: vaultTokenAcc,
-                                    tokenProgram: TOKEN_PROGRAM_ID,
-                                }).rpc();
+
+                                for (const v of winning) {
+                                    await program.methods.claimWinnings().accounts({
+                                        config: configPda,
+                                        mint: BETTING_MINT,
+                                        market: marketPda,
+                                        vote: v.publicKey,
+                                        marketVault,
+                                        userToken: userTokenAcc,
+                                        devToken: configAccount.feeRecipient,
+                                        user: publicKey,
+                                        tokenProgram: TOKEN_PROGRAM_ID,
+                                    } as any).rpc();
+                                }
 
                                 toast.success("Winnings Claimed Successfully!", { id: toastId });
                                 if (onSettle) onSettle(id);
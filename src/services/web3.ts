@@ -30,9 +30,13 @@ export const BETTING_MINT = safePK(process.env.NEXT_PUBLIC_BETTING_MINT || '4kTw
 // Treasury Wallet for collecting protocol taxes
 export const TREASURY_WALLET = safePK(process.env.NEXT_PUBLIC_TREASURY_WALLET || '2KF9SAvpU2h2ZhczzMLbgx7arkjG8QHCXbQ6XaDqtEtm');
 
+// Optimistic resolution defaults: proposer/disputer bond (6-decimal base units) and dispute window
+export const RESOLUTION_BOND = 1_000_000_000;
+export const DISPUTE_WINDOW_SECONDS = 86_400;
+
 // Token Program IDs
 export const SPL_ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID = new PublicKey('ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL');
-export const TOKEN_PROGRAM_ID = new PublicKey('TokenzQdBnBLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb'); // Token-2022 (Correct for $PREDICT)
+export const TOKEN_PROGRAM_ID = new PublicKey('TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb'); // Token-2022 (Correct for $PREDICT)
 export const LEGACY_TOKEN_PROGRAM_ID = new PublicKey('TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA');
 
 export const getATA = async (owner: PublicKey, mint: PublicKey) => {
@@ -88,17 +92,27 @@ export const getMarketPDA = async (marketId: number) => {
     );
 };
 
-export const getVotePDA = async (market: PublicKey, user: PublicKey) => {
+// A wallet can hold several positions per market, one vote PDA per nonce
+export const getVotePDA = async (market: PublicKey, user: PublicKey, nonce: BN) => {
     return await PublicKey.findProgramAddress(
         [
             Buffer.from("vote"),
             market.toBuffer(),
-            user.toBuffer()
+            user.toBuffer(),
+            nonce.toArrayLike(Buffer, 'le', 8)
         ],
         PROGRAM_ID
     );
 };
 
+// Every position `user` holds on `market` (Vote layout: discriminator, user, market)
+export const getUserVotes = async (program: Program, market: PublicKey, user: PublicKey) => {
+    return await (program.account as any).vote.all([
+        { memcmp: { offset: 8, bytes: user.toBase58() } },
+        { memcmp: { offset: 40, bytes: market.toBase58() } },
+    ]);
+};
+
 export const getConfigPDA = async () => {
     const [config] = await PublicKey.findProgramAddress(
         [Buffer.from("config")],
@@ -107,9 +121,17 @@ export const getConfigPDA = async () => {
     return config;
 };
 
+export const getMarketVaultPDA = async (market: PublicKey) => {
+    const [vault] = await PublicKey.findProgramAddress(
+        [Buffer.from("market_vault"), market.toBuffer()],
+        PROGRAM_ID
+    );
+    return vault;
+};
+
 export const getTreasuryVaultPDA = async () => {
     const [vault] = await PublicKey.findProgramAddress(
-        [Buffer.from("treasury")],
+        [Buffer.from("vault")],
         PROGRAM_ID
     );
     return vault;
@@ -117,7 +139,13 @@ export const getTreasuryVaultPDA = async () => {
 
 // --- Transaction Helpers ---
 
-export const initializeProtocol = async (wallet: any, mint: PublicKey) => {
+export const initializeProtocol = async (
+    wallet: any,
+    mint: PublicKey,
+    feeRecipient: PublicKey,
+    resolutionBond: number,
+    disputeWindowSeconds: number
+) => {
     const program = getProgram(wallet);
     if (!program) throw new Error("Wallet not connected");
 
@@ -125,11 +153,12 @@ export const initializeProtocol = async (wallet: any, mint: PublicKey) => {
     const treasuryVault = await getTreasuryVaultPDA();
 
     return await program.methods
-        .initializeProtocol()
+        .initializeProtocol(new BN(resolutionBond), new BN(disputeWindowSeconds))
         .accounts({
             config,
             treasuryVault,
             mint,
+            feeRecipient,
             authority: wallet.publicKey,
             systemProgram: SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
@@ -140,11 +169,15 @@ export const initializeProtocol = async (wallet: any, mint: PublicKey) => {
 
 export const initializeMarketOnChain = async (
     wallet: any,
-    question: String,
+    question: string,
     endTimestamp: number,
     outcomesCount: number,
     virtualLiquidity: number,
-    weights: number[]
+    weights: number[],
+    outcomeNames: string[],
+    polymarketId: string = "",
+    minBet: number = 1,
+    maxBet: number = 1_000_000_000_000
 ) => {
     const program = getProgram(wallet);
     if (!program) throw new Error("Wallet not connected");
@@ -164,12 +197,25 @@ export const initializeMarketOnChain = async (
             new BN(endTimestamp),
             outcomesCount,
             new BN(virtualLiquidity),
-            paddedWeights
+            paddedWeights,
+            wallet.publicKey, // oracle: the creator resolves its own markets
+            outcomeNames,
+            new BN(minBet),
+            new BN(maxBet),
+            polymarketId,
+            "", // metadataUrl
+            { standard: {} },
+            { fixedOdds: {} }
         )
         .accounts({
             market,
+            marketVault: await getMarketVaultPDA(market),
+            config: await getConfigPDA(),
+            mint: BETTING_MINT,
+            creatorToken: await getATA(wallet.publicKey, BETTING_MINT),
             authority: wallet.publicKey,
             systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
         })
         .rpc();
 };
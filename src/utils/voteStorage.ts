@@ -1,5 +1,5 @@
 import { Connection, PublicKey, Transaction, SystemProgram } from '@solana/web3.js';
-import { getProgram, getProvider, PROGRAM_ID, TOKEN_PROGRAM_ID, getVotePDA, getATA, BETTING_MINT } from '@/services/web3';
+import { getProgram, getProvider, PROGRAM_ID, TOKEN_PROGRAM_ID, getVotePDA, getATA, getConfigPDA, getMarketVaultPDA, BETTING_MINT } from '@/services/web3';
 import { BN } from '@coral-xyz/anchor';
 
 export interface Vote {
@@ -41,19 +41,23 @@ export async function saveVote(vote: Vote, wallet: any): Promise<string | void>
                 throw new Error("Invalid Market Account");
             }
 
-            const votePda = await getVotePDA(marketKey, wallet.publicKey);
+            // Every bet opens its own position, so it needs an unused nonce
+            const nonce = new BN(Date.now());
+            const [votePda] = await getVotePDA(marketKey, wallet.publicKey, nonce);
 
             // Derive Token Accounts
             const userToken = await getATA(wallet.publicKey, BETTING_MINT);
-            const vaultToken = await getATA(marketKey, BETTING_MINT);
+            const marketVault = await getMarketVaultPDA(marketKey);
 
             // Call the Smart Contract
-            const tx = await program.methods.placeVote(outcomeIndex, new BN(amount))
+            const tx = await program.methods.placeVote(outcomeIndex, new BN(amount), nonce)
                 .accounts({
                     market: marketKey,
-                    vote: (votePda as any)[0], // getVotePDA returns [pda, bump]
-                    vaultToken: vaultToken,
+                    vote: votePda,
+                    marketVault,
                     userToken: userToken,
+                    config: await getConfigPDA(),
+                    mint: BETTING_MINT,
                     user: wallet.publicKey,
                     systemProgram: SystemProgram.programId,
                     tokenProgram: TOKEN_PROGRAM_ID,